@@ -0,0 +1,306 @@
+// ============================================================================
+// CLI Argument Assembly
+// ============================================================================
+// Turns a generated form into the argument list passed to the WASM function.
+// cli-ui.js uses it for the bundled UI; other frontends can load this script
+// on its own and call
+//
+//     window.buildCliArgs(config, formElement)
+//
+// with the page config (window.CLI_CONFIG) and the generated `<form>`.
+// window.createArgBuilder(config, formElement) returns the helpers it's built
+// from (reading field values, flag tokens, tokenizing), bound to that form.
+(function () {
+    // Selector value of the free-form subcommand (allow_external_subcommands)
+    const EXTERNAL_SUBCOMMAND = '__external__';
+    // Prefix of the fields every subcommand shares, rendered once in their own
+    // section (shared_subcommand_fields)
+    const SHARED_FIELDS = '__shared__';
+
+    // Splits a command line into tokens, honoring quotes and backslash escapes
+    function tokenizeCommandLine(text) {
+        const tokens = [];
+        let current = '';
+        let inToken = false;
+        let quote = null;
+
+        for (let i = 0; i < text.length; i++) {
+            const ch = text[i];
+            if (quote) {
+                if (ch === quote) {
+                    quote = null;
+                } else if (ch === '\\' && quote === '"' && i + 1 < text.length) {
+                    current += text[++i];
+                } else {
+                    current += ch;
+                }
+            } else if (ch === '"' || ch === "'") {
+                quote = ch;
+                inToken = true;
+            } else if (ch === '\\' && i + 1 < text.length) {
+                current += text[++i];
+                inToken = true;
+            } else if (/\s/.test(ch)) {
+                if (inToken) tokens.push(current);
+                current = '';
+                inToken = false;
+            } else {
+                current += ch;
+                inToken = true;
+            }
+        }
+
+        if (quote) {
+            throw new Error(`Unterminated ${quote} quote`);
+        }
+        if (inToken) tokens.push(current);
+        return tokens;
+    }
+
+    function createArgBuilder(config, formElement) {
+        const fields = config.fields;
+        const subcommands = config.subcommands || [];
+        const byId = id => formElement.querySelector(`#${CSS.escape(id)}`);
+        const sharedFieldNames = new Set(Array.from(
+            formElement.querySelectorAll(`#subcommand-${SHARED_FIELDS} :is(.field-group, .segment)[data-field-name]`),
+            element => element.dataset.fieldName));
+
+        // Element id of a field in a section: the main command ('') or a
+        // subcommand. A subcommand's shared fields live in the shared section.
+        function fieldElementId(prefix, name) {
+            if (!prefix) return name;
+            if (prefix !== SHARED_FIELDS && sharedFieldNames.has(name)) return `${SHARED_FIELDS}-${name}`;
+            return `${prefix}-${name}`;
+        }
+
+        function isNestedVecField(field) {
+            return Boolean((config.nested_fields || {})[field.name]);
+        }
+
+        function isCheckboxSetField(field) {
+            return Boolean((config.checkbox_sets || {})[field.name]);
+        }
+
+        function getCheckboxSetBoxes(fieldId) {
+            return Array.from(byId(fieldId).querySelectorAll('input[type="checkbox"]'));
+        }
+
+        function getCheckboxSetValues(fieldId) {
+            return getCheckboxSetBoxes(fieldId).filter(box => box.checked).map(box => box.value);
+        }
+
+        function getVecValues(fieldName) {
+            const container = byId(`${fieldName}-items`);
+            return Array.from(container.children).map(item =>
+                item.textContent.slice(0, -1) // Remove the × character
+            );
+        }
+
+        function getNestedVecValues(fieldId) {
+            const groups = byId(`${fieldId}-groups`);
+            return Array.from(groups.children)
+                .map(group => Array.from(group.querySelector('.vec-items').children).map(item =>
+                    item.textContent.slice(0, -1) // Remove the × character
+                ))
+                .filter(values => values.length > 0);
+        }
+
+        // The literal a value-taking bool emits for its checkbox state
+        function boolLiteral(field, checked) {
+            return checked ? field.bool_values.true_value : field.bool_values.false_value;
+        }
+
+        // Returns the values a value-taking field currently holds (empty when unset)
+        function getFieldValues(field, elementId) {
+            if (isNestedVecField(field)) return getNestedVecValues(elementId);
+            if (isCheckboxSetField(field)) return getCheckboxSetValues(elementId);
+            if (field.field_type.type === 'Vec') return getVecValues(elementId);
+
+            const element = byId(elementId);
+            if (field.field_type.type === 'Bool') {
+                return field.bool_values && element ? [boolLiteral(field, element.checked)] : [];
+            }
+            const value = element ? element.value.trim() : '';
+            return value ? [value] : [];
+        }
+
+        // Positional fields of one list, in the order clap assigns them (by
+        // index), with last(true) positionals at the end
+        function positionalFields(fields) {
+            return fields
+                .filter(f => f.is_positional)
+                .sort((a, b) => (a.last - b.last) || ((a.index ?? Infinity) - (b.index ?? Infinity)));
+        }
+
+        // The flag a field is emitted with, per config.flag_preference
+        function flagFor(field) {
+            const preferShort = config.flag_preference !== 'AlwaysLong';
+            if (field.short && (preferShort || !field.long)) return `-${field.short}`;
+            return `--${field.long}`;
+        }
+
+        // The occurrences of a non-positional field's flag, each as the list
+        // of values that follow the flag (empty for switches and counters)
+        function flagOccurrences(field, elementId) {
+            const element = byId(elementId);
+            if (!element) return [];
+
+            if (isCheckboxSetField(field)) {
+                // One flag occurrence per checked box
+                return getCheckboxSetValues(elementId).map(value => [value]);
+            }
+
+            if (isNestedVecField(field)) {
+                const nested = config.nested_fields[field.name];
+                // One flag occurrence per group: joined by the delimiter, or as separate values
+                return getNestedVecValues(elementId).map(group =>
+                    nested.delimiter ? [group.join(nested.delimiter)] : group
+                );
+            }
+
+            switch (field.field_type.type) {
+                case 'Bool':
+                    if (field.bool_values) {
+                        // The flag takes a value: always pass the literal the parser expects
+                        return [[boolLiteral(field, element.checked)]];
+                    }
+                    return element.checked ? [[]] : [];
+
+                case 'Counter':
+                    const count = parseInt(element.value) || 0;
+                    return Array.from({ length: Math.max(count, 0) }, () => []);
+
+                case 'Vec': {
+                    const values = getVecValues(elementId);
+                    // A value_terminator arg takes all its values in one occurrence
+                    if (field.value_terminator) return values.length > 0 ? [withTerminator(field, values)] : [];
+                    return values.map(value => [value]);
+                }
+
+                default: // String, Integer, Enum
+                    const value = element.value.trim();
+                    return value ? [[value]] : [];
+            }
+        }
+
+        // A variadic field's values followed by its value_terminator, if it
+        // has one, so later arguments aren't taken as more of its values
+        function withTerminator(field, values) {
+            return field.value_terminator && values.length > 0 ? [...values, field.value_terminator] : values;
+        }
+
+        // The tokens of one flag occurrence; require_equals attaches the value
+        // to the flag (`--flag=value`), as does a hyphen-leading value of an
+        // allow_hyphen_values field when the flag is long, so it can't be read
+        // as a flag
+        function flagTokens(field, values) {
+            const flag = flagFor(field);
+            const hyphenValue = field.allow_hyphen_values && flag.startsWith('--')
+                && values.length > 0 && values[0].startsWith('-');
+            if ((field.require_equals || hyphenValue) && values.length > 0) {
+                return [`${flag}=${values[0]}`, ...values.slice(1)];
+            }
+            return [flag, ...values];
+        }
+
+        // Appends the flag tokens for a non-positional field to args
+        function pushFlagArgs(field, elementId, args) {
+            flagOccurrences(field, elementId).forEach(values => {
+                args.push(...flagTokens(field, values));
+            });
+        }
+
+        // Appends the values of a field list's positionals, in index order.
+        // Empty positionals are never emitted (validation rejects gaps), and
+        // the values of a last(true) positional go after a `--`.
+        function pushPositionalArgs(fields, prefix, args) {
+            positionalFields(fields).forEach(field => {
+                const elementId = fieldElementId(prefix, field.name);
+                if (!byId(elementId)) return;
+                const values = getFieldValues(field, elementId);
+                if (field.last && values.length > 0) {
+                    args.push('--');
+                }
+                args.push(...withTerminator(field, values));
+            });
+        }
+
+        // The subcommand picked in the form, if any
+        function selectedSubcommand() {
+            const selector = byId('subcommand-selector');
+            return (selector && selector.value) || null;
+        }
+
+        // The full argument list for the form's current state
+        function buildArgs() {
+            const args = [];
+
+            // Process main command flags
+            fields.forEach(field => {
+                if (!field.is_positional && byId(field.name)) {
+                    pushFlagArgs(field, field.name, args);
+                }
+            });
+
+            // Add positional args before subcommand
+            pushPositionalArgs(fields, '', args);
+
+            // An external subcommand takes everything after its name
+            const subcommand = selectedSubcommand();
+            if (subcommand === EXTERNAL_SUBCOMMAND) {
+                args.push(byId('external-subcommand-name').value.trim());
+                args.push(...tokenizeCommandLine(byId('external-subcommand-args').value));
+            } else if (subcommand) {
+                args.push(subcommand);
+
+                // Find the subcommand config
+                const subcommandConfig = subcommands.find(sc => sc.name === subcommand);
+                if (subcommandConfig) {
+                    subcommandConfig.fields.forEach(field => {
+                        const elementId = fieldElementId(subcommand, field.name);
+                        if (!field.is_positional && byId(elementId)) {
+                            pushFlagArgs(field, elementId, args);
+                        }
+                    });
+
+                    // Add subcommand positional args at the end
+                    pushPositionalArgs(subcommandConfig.fields, subcommand, args);
+                }
+            }
+
+            // Append the raw extra arguments
+            const extraArgs = byId('extra-args');
+            if (extraArgs) {
+                args.push(...tokenizeCommandLine(extraArgs.value));
+            }
+
+            return args;
+        }
+
+        return {
+            EXTERNAL_SUBCOMMAND,
+            SHARED_FIELDS,
+            fieldElementId,
+            isNestedVecField,
+            isCheckboxSetField,
+            getCheckboxSetBoxes,
+            getCheckboxSetValues,
+            getVecValues,
+            getNestedVecValues,
+            boolLiteral,
+            getFieldValues,
+            positionalFields,
+            flagFor,
+            flagOccurrences,
+            withTerminator,
+            flagTokens,
+            tokenizeCommandLine,
+            selectedSubcommand,
+            buildArgs,
+        };
+    }
+
+    window.createArgBuilder = createArgBuilder;
+    // Throws if a free-form arguments box has an unterminated quote
+    window.buildCliArgs = (config, formElement) => createArgBuilder(config, formElement).buildArgs();
+})();
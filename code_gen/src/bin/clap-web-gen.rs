@@ -1,30 +1,163 @@
-/// Code generator CLI tool for discovering and generating web UIs
-///
-/// This tool scans Rust source files for #[web_ui_bind] annotations and
-/// automatically generates HTML files for web UIs.
-///
-/// Usage:
-///   From your project directory (where you use #[web_ui_bind]):
-///     cargo run --package code_gen --bin clap-web-gen
-///
-/// Or install globally:
-///     cargo install --path code_gen
-///     cd your_project && clap-web-gen
-
+//! Code generator CLI tool for discovering and generating web UIs
+//!
+//! This tool scans Rust source files for #[web_ui_bind] annotations and
+//! automatically generates HTML files for web UIs.
+//!
+//! Usage:
+//!   From your project directory (where you use #[web_ui_bind]):
+//!     cargo run --package code_gen --bin clap-web-gen
+//!
+//! Or install globally:
+//!     cargo install --path code_gen
+//!     cd your_project && clap-web-gen
+//!
+//! Options:
+//!   --only-codegen     Write the generator source without running it
+//!   --external-config  Write each page's config to pkg/<name>.config.json and
+//!                      have the page fetch it, and its scripts to
+//!                      pkg/<name>.ui.js, pkg/i18n.js and pkg/arg-builder.js,
+//!                      so the page has no inline script
+//!   --config-module    Write each page's config to pkg/<name>.config.js as an
+//!                      ES module the page imports (takes precedence over
+//!                      --external-config)
+//!   --json-schema      Also write a JSON Schema of each function's arguments
+//!                      to pkg/<name>.schema.json
+//!   --external-css     Write the stylesheet once to pkg/cli-ui.css and link
+//!                      it from every page, instead of inlining it
+//!   --zip <path>       Also bundle the generated files, plus the wasm-pack
+//!                      module (<package>.js, <package>_bg.wasm), into a zip
+
+use std::fmt;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use syn::{File, Item, ItemFn};
 
+/// Failure modes of the generator; `main` prints them and exits with status 1
+#[derive(Debug)]
+enum GenError {
+    /// The current directory couldn't be determined
+    CurrentDir(io::Error),
+    /// There is no src/ directory to scan
+    NoSrcDir,
+    /// Bound functions live in main.rs, where the generator can't call them
+    FunctionsInMain {
+        package_name: String,
+        functions: Vec<String>,
+    },
+    /// Two functions write the same HTML file
+    HtmlNameConflict {
+        html_name: String,
+        first: String,
+        second: String,
+    },
+    /// Writing the generated files failed
+    Io {
+        context: &'static str,
+        source: io::Error,
+    },
+    /// `cargo build --lib` couldn't be started
+    Build(io::Error),
+    /// An option that takes a value was given without one
+    MissingValue(&'static str),
+}
+
+impl fmt::Display for GenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenError::CurrentDir(e) => write!(f, "Error: Failed to get current directory: {}", e),
+            GenError::NoSrcDir => {
+                writeln!(f, "Error: No src/ directory found")?;
+                write!(f, "Please run this from your project root")
+            }
+            GenError::FunctionsInMain { package_name, functions } => {
+                writeln!(f, "\nError: #[web_ui_bind] functions found in main.rs")?;
+                writeln!(f, "Functions in main.rs are part of the binary target and cannot")?;
+                writeln!(f, "be used by the web UI generator.\n")?;
+                writeln!(f, "The following functions need to be moved to lib.rs or a library module:")?;
+                for name in functions {
+                    writeln!(f, "  - {}", name)?;
+                }
+                writeln!(f, "\nSolution:")?;
+                writeln!(f, "1. Move your CLI struct and #[web_ui_bind] function to src/lib.rs")?;
+                writeln!(f, "2. Re-export them in main.rs if needed: pub use {}::{{Cli, run}};", package_name)?;
+                writeln!(f, "3. Update main.rs to call the function from the library")
+            }
+            GenError::HtmlNameConflict { html_name, first, second } => {
+                writeln!(f, "\nError: HTML filename conflict detected!")?;
+                writeln!(f, "Multiple functions are configured to generate 'pkg/{}':", html_name)?;
+                writeln!(f, "  - Function '{}' ", first)?;
+                writeln!(f, "  - Function '{}' ", second)?;
+                writeln!(f, "\nSolution:")?;
+                writeln!(f, "Specify different HTML filenames using the html_name parameter:")?;
+                writeln!(f, "  #[web_ui_bind(html_name = \"function1.html\")]")?;
+                writeln!(f, "  #[web_ui_bind(html_name = \"function2.html\")]")
+            }
+            GenError::Io { context, source } => write!(f, "\nError: {}: {}", context, source),
+            GenError::Build(e) => write!(f, "\nFailed to build project: {}", e),
+            GenError::MissingValue(option) => write!(f, "Error: {} needs a value", option),
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
+/// Command-line flags of the generator
+#[derive(Debug, Default)]
+struct Options {
+    /// `--only-codegen`
+    only_codegen: bool,
+    /// `--external-config`
+    external_config: bool,
+    /// `--config-module`
+    config_module: bool,
+    /// `--json-schema`
+    json_schema: bool,
+    /// `--external-css`
+    external_css: bool,
+    /// `--zip <path>`
+    zip: Option<PathBuf>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, GenError> {
+        let zip = match args.iter().position(|a| a == "--zip") {
+            Some(i) => Some(args.get(i + 1).map(PathBuf::from).ok_or(GenError::MissingValue("--zip"))?),
+            None => None,
+        };
+        Ok(Options {
+            only_codegen: args.iter().any(|a| a == "--only-codegen"),
+            external_config: args.iter().any(|a| a == "--external-config"),
+            config_module: args.iter().any(|a| a == "--config-module"),
+            json_schema: args.iter().any(|a| a == "--json-schema"),
+            external_css: args.iter().any(|a| a == "--external-css"),
+            zip,
+        })
+    }
+
+    /// Whether the pages load their scripts from files: with the config out
+    /// of the page too, nothing is left inline
+    fn external_scripts(&self) -> bool {
+        self.external_config || self.config_module
+    }
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
-    let only_codegen = args.iter().any(|a| a == "--only-codegen");
 
+    if let Err(e) = Options::parse(&args).and_then(|options| run(&options)) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(options: &Options) -> Result<(), GenError> {
     println!("Web UI Generator");
     println!("Scanning for #[web_ui_bind] functions...\n");
 
     // Get current directory (should be run from project root)
-    let current_dir = std::env::current_dir().expect("Failed to get current directory");
+    let current_dir = std::env::current_dir().map_err(GenError::CurrentDir)?;
 
     // Find the package name from Cargo.toml
     let package_name = get_package_name(&current_dir);
@@ -33,9 +166,7 @@ fn main() {
     // Find all Rust source files
     let src_dir = current_dir.join("src");
     if !src_dir.exists() {
-        eprintln!("Error: No src/ directory found");
-        eprintln!("Please run this from your project root");
-        std::process::exit(1);
+        return Err(GenError::NoSrcDir);
     }
 
     let src_files = find_rust_files(&src_dir);
@@ -47,94 +178,80 @@ fn main() {
     if bound_functions.is_empty() {
         println!("\nNo #[web_ui_bind] functions found");
         println!("Add #[web_ui_bind] to your functions to generate web UIs\n");
-        std::process::exit(0);
+        return Ok(());
     }
 
-    // Check if any functions are in main.rs (binary target)
-    let binary_functions: Vec<_> = bound_functions
-        .iter()
-        .filter(|f| f.module_path == "__BINARY_TARGET__")
-        .collect();
-
-    if !binary_functions.is_empty() {
-        eprintln!("\nError: #[web_ui_bind] functions found in main.rs");
-        eprintln!("Functions in main.rs are part of the binary target and cannot");
-        eprintln!("be used by the web UI generator.\n");
-        eprintln!("The following functions need to be moved to lib.rs or a library module:");
-        for func in &binary_functions {
-            eprintln!("  - {}", func.name);
-        }
-        eprintln!("\nSolution:");
-        eprintln!("1. Move your CLI struct and #[web_ui_bind] function to src/lib.rs");
-        eprintln!("2. Re-export them in main.rs if needed: pub use {}::{{Cli, run}};", package_name);
-        eprintln!("3. Update main.rs to call the function from the library\n");
-        std::process::exit(1);
-    }
+    check_functions_not_in_main(&bound_functions, &package_name)?;
 
     println!("\nFound {} function(s) with #[web_ui_bind]:", bound_functions.len());
     for func in &bound_functions {
-        println!("  - {} -> pkg/{}", func.name, func.html_name);
+        let outputs: Vec<String> = function_output_files(func, options)
+            .iter()
+            .map(|file| format!("pkg/{}", file))
+            .collect();
+        println!("  - {} -> {}", func.name, outputs.join(", "));
     }
-
-    // Check for HTML filename conflicts
-    let mut html_names = std::collections::HashMap::new();
-    for func in &bound_functions {
-        if let Some(existing) = html_names.insert(&func.html_name, &func.name) {
-            eprintln!("\nError: HTML filename conflict detected!");
-            eprintln!("Multiple functions are configured to generate 'pkg/{}':", func.html_name);
-            eprintln!("  - Function '{}' ", existing);
-            eprintln!("  - Function '{}' ", func.name);
-            eprintln!("\nSolution:");
-            eprintln!("Specify different HTML filenames using the html_name parameter:");
-            eprintln!("  #[web_ui_bind(html_name = \"function1.html\")]");
-            eprintln!("  #[web_ui_bind(html_name = \"function2.html\")]\n");
-            std::process::exit(1);
-        }
+    if options.external_css {
+        println!("  Shared stylesheet -> pkg/{}", CSS_FILE_NAME);
+    }
+    if options.external_scripts() {
+        println!("  Shared scripts -> pkg/{}, pkg/{}", I18N_FILE_NAME, ARG_BUILDER_FILE_NAME);
     }
 
+    check_html_name_conflicts(&bound_functions)?;
+
     // Generate the UI generator source file in target directory (gitignored)
-    let generator_code = generate_ui_generator_code(&package_name, &bound_functions);
+    let generator_code = generate_ui_generator_code(&package_name, &bound_functions, options);
 
     // Write to target/clap-web-gen/ directory (not src/, to avoid noise)
     let gen_dir = current_dir.join("target/clap-web-gen");
-    fs::create_dir_all(&gen_dir).expect("Failed to create target/clap-web-gen directory");
+    fs::create_dir_all(&gen_dir).map_err(|source| GenError::Io {
+        context: "Failed to create target/clap-web-gen directory",
+        source,
+    })?;
 
     let generator_path = gen_dir.join("ui_generator.rs");
-    fs::write(&generator_path, generator_code)
-        .expect("Failed to write generator file");
+    fs::write(&generator_path, generator_code).map_err(|source| GenError::Io {
+        context: "Failed to write generator file",
+        source,
+    })?;
 
-    if only_codegen {
+    if options.only_codegen {
         println!("\nCode generation complete!");
         println!("Temporary file: target/clap-web-gen/ui_generator.rs");
-        return;
+        return Ok(());
     }
 
     // Automatically compile and run the generator to create HTML files
     println!("\nCompiling and running generator...");
 
     // First, build the project to ensure dependencies are available
-    let build_status = Command::new("cargo")
+    Command::new("cargo")
         .arg("build")
         .arg("--lib")
         .current_dir(&current_dir)
-        .status();
-
-    if let Err(e) = build_status {
-        eprintln!("\nFailed to build project: {}", e);
-        std::process::exit(1);
-    }
+        .status()
+        .map_err(GenError::Build)?;
 
     // Compile the temporary generator using cargo-script approach
     let status = Command::new("cargo")
         .arg("run")
         .arg("--manifest-path")
-        .arg(create_temp_manifest(&gen_dir, &package_name, &current_dir))
+        .arg(create_temp_manifest(&gen_dir, &package_name, &current_dir)?)
         .current_dir(&current_dir)
         .status();
 
     match status {
         Ok(exit_status) if exit_status.success() => {
             println!("\nHTML generation finished.");
+            if let Some(zip_path) = &options.zip {
+                let files = zip_file_names(&package_name, &bound_functions, options, &current_dir.join("pkg"));
+                write_zip(zip_path, &current_dir.join("pkg"), &files).map_err(|source| GenError::Io {
+                    context: "Failed to write zip archive",
+                    source,
+                })?;
+                println!("Bundled {} file(s) into {}", files.len(), zip_path.display());
+            }
         }
         Ok(_) => {
             eprintln!("\nHTML generation failed");
@@ -143,6 +260,41 @@ fn main() {
             eprintln!("\nFailed to run generator: {}", e);
         }
     }
+
+    Ok(())
+}
+
+/// Functions in main.rs belong to the binary target, which the generator can't link
+fn check_functions_not_in_main(functions: &[BoundFunction], package_name: &str) -> Result<(), GenError> {
+    let binary_functions: Vec<String> = functions
+        .iter()
+        .filter(|f| f.module_path == "__BINARY_TARGET__")
+        .map(|f| f.name.clone())
+        .collect();
+
+    if binary_functions.is_empty() {
+        Ok(())
+    } else {
+        Err(GenError::FunctionsInMain {
+            package_name: package_name.to_string(),
+            functions: binary_functions,
+        })
+    }
+}
+
+/// Each function must write its own HTML file
+fn check_html_name_conflicts(functions: &[BoundFunction]) -> Result<(), GenError> {
+    let mut html_names = std::collections::HashMap::new();
+    for func in functions {
+        if let Some(existing) = html_names.insert(&func.html_name, &func.name) {
+            return Err(GenError::HtmlNameConflict {
+                html_name: func.html_name.clone(),
+                first: existing.clone(),
+                second: func.name.clone(),
+            });
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -220,15 +372,15 @@ fn extract_web_ui_bind_functions(ast: &File, module_path: &str) -> Vec<BoundFunc
     let mut functions = Vec::new();
 
     for item in &ast.items {
-        if let Item::Fn(item_fn) = item {
-            if let Some(html_name) = get_web_ui_bind_html_name(item_fn) {
-                let name = item_fn.sig.ident.to_string();
-                functions.push(BoundFunction {
-                    name,
-                    module_path: module_path.to_string(),
-                    html_name,
-                });
-            }
+        if let Item::Fn(item_fn) = item
+            && let Some(html_name) = get_web_ui_bind_html_name(item_fn)
+        {
+            let name = item_fn.sig.ident.to_string();
+            functions.push(BoundFunction {
+                name,
+                module_path: module_path.to_string(),
+                html_name,
+            });
         }
     }
 
@@ -237,33 +389,33 @@ fn extract_web_ui_bind_functions(ast: &File, module_path: &str) -> Vec<BoundFunc
 
 fn get_web_ui_bind_html_name(item_fn: &ItemFn) -> Option<String> {
     for attr in &item_fn.attrs {
-        if let Some(ident) = attr.path().get_ident() {
-            if ident == "web_ui_bind" {
-                // Parse the attribute arguments
-                if let Ok(meta_list) = attr.meta.require_list() {
-                    // Parse tokens as nested meta items
-                    let tokens = &meta_list.tokens;
-                    let tokens_str = tokens.to_string();
-
-                    // Simple parsing: look for html_name = "value"
-                    if let Some(start) = tokens_str.find("html_name") {
-                        let after_name = &tokens_str[start..];
-                        if let Some(eq_pos) = after_name.find('=') {
-                            let after_eq = after_name[eq_pos + 1..].trim();
-                            // Extract quoted string
-                            if let Some(value) = extract_quoted_string(after_eq) {
-                                return Some(value);
-                            }
+        if let Some(ident) = attr.path().get_ident()
+            && ident == "web_ui_bind"
+        {
+            // Parse the attribute arguments
+            if let Ok(meta_list) = attr.meta.require_list() {
+                // Parse tokens as nested meta items
+                let tokens = &meta_list.tokens;
+                let tokens_str = tokens.to_string();
+
+                // Simple parsing: look for html_name = "value"
+                if let Some(start) = tokens_str.find("html_name") {
+                    let after_name = &tokens_str[start..];
+                    if let Some(eq_pos) = after_name.find('=') {
+                        let after_eq = after_name[eq_pos + 1..].trim();
+                        // Extract quoted string
+                        if let Some(value) = extract_quoted_string(after_eq) {
+                            return Some(value);
                         }
                     }
-                } else if attr.meta.require_path_only().is_ok() {
-                    // No arguments, use default
-                    return Some("index.html".to_string());
                 }
-
-                // If we found the attribute but couldn't parse args, use default
+            } else if attr.meta.require_path_only().is_ok() {
+                // No arguments, use default
                 return Some("index.html".to_string());
             }
+
+            // If we found the attribute but couldn't parse args, use default
+            return Some("index.html".to_string());
         }
     }
     None
@@ -271,15 +423,157 @@ fn get_web_ui_bind_html_name(item_fn: &ItemFn) -> Option<String> {
 
 fn extract_quoted_string(s: &str) -> Option<String> {
     let s = s.trim();
-    if s.starts_with('"') {
-        if let Some(end_quote) = s[1..].find('"') {
-            return Some(s[1..=end_quote].to_string());
-        }
+    if s.starts_with('"')
+        && let Some(end_quote) = s[1..].find('"')
+    {
+        return Some(s[1..=end_quote].to_string());
     }
     None
 }
 
-fn generate_ui_generator_code(package_name: &str, functions: &[BoundFunction]) -> String {
+/// Stylesheet shared by all pages with `--external-css`
+const CSS_FILE_NAME: &str = "cli-ui.css";
+
+/// Translations script shared by all pages when their scripts are external
+const I18N_FILE_NAME: &str = "i18n.js";
+
+/// Argument assembly script shared by all pages when their scripts are external
+const ARG_BUILDER_FILE_NAME: &str = "arg-builder.js";
+
+/// Files (relative to pkg/) generated for one function: its page plus the
+/// enabled sidecar files
+fn function_output_files(func: &BoundFunction, options: &Options) -> Vec<String> {
+    let mut files = vec![func.html_name.clone()];
+    if options.config_module {
+        files.push(sidecar_file_name(&func.html_name, "config.js"));
+    } else if options.external_config {
+        files.push(sidecar_file_name(&func.html_name, "config.json"));
+    }
+    if options.external_scripts() {
+        files.push(sidecar_file_name(&func.html_name, "ui.js"));
+    }
+    if options.json_schema {
+        files.push(sidecar_file_name(&func.html_name, "schema.json"));
+    }
+    files
+}
+
+/// Files (relative to `pkg_dir`) bundled by `--zip`: every generated file,
+/// plus the wasm-pack module the pages import, if it has been built
+fn zip_file_names(package_name: &str, functions: &[BoundFunction], options: &Options, pkg_dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = functions.iter()
+        .flat_map(|func| function_output_files(func, options))
+        .collect();
+    if options.external_css {
+        files.push(CSS_FILE_NAME.to_string());
+    }
+    if options.external_scripts() {
+        files.push(I18N_FILE_NAME.to_string());
+        files.push(ARG_BUILDER_FILE_NAME.to_string());
+    }
+
+    let module = package_name.replace('-', "_");
+    for file in [format!("{}.js", module), format!("{}_bg.wasm", module)] {
+        if pkg_dir.join(&file).exists() {
+            files.push(file);
+        } else {
+            eprintln!("Warning: pkg/{} not found; run wasm-pack build to include it in the zip", file);
+        }
+    }
+    files
+}
+
+/// Writes a zip archive of `files` (paths relative to `base`, kept as the
+/// entry names). Entries are stored uncompressed, which every unzip tool reads.
+fn write_zip(zip_path: &Path, base: &Path, files: &[String]) -> io::Result<()> {
+    fn size(len: usize) -> io::Result<u32> {
+        u32::try_from(len).map_err(|_| io::Error::other("file too large for a zip archive"))
+    }
+    fn count(len: usize, what: &str) -> io::Result<u16> {
+        u16::try_from(len).map_err(|_| io::Error::other(format!("{} too long for a zip archive", what)))
+    }
+    // DOS date of 1980-01-01, the earliest zip supports
+    const DOS_DATE: u16 = 0x21;
+
+    let entries = count(files.len(), "file list")?;
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for name in files {
+        let name_len = count(name.len(), "file name")?;
+        let data = fs::read(base.join(name))?;
+        let crc = crc32(&data);
+        let offset = size(archive.len())?;
+
+        // Local file header and data
+        archive.extend(0x0403_4b50u32.to_le_bytes());
+        archive.extend(20u16.to_le_bytes()); // version needed
+        archive.extend(0u16.to_le_bytes()); // flags
+        archive.extend(0u16.to_le_bytes()); // method: stored
+        archive.extend(0u16.to_le_bytes()); // time
+        archive.extend(DOS_DATE.to_le_bytes());
+        archive.extend(crc.to_le_bytes());
+        archive.extend(size(data.len())?.to_le_bytes()); // compressed size
+        archive.extend(size(data.len())?.to_le_bytes()); // uncompressed size
+        archive.extend(name_len.to_le_bytes());
+        archive.extend(0u16.to_le_bytes()); // extra field length
+        archive.extend(name.as_bytes());
+        archive.extend(&data);
+
+        // Central directory entry
+        central_directory.extend(0x0201_4b50u32.to_le_bytes());
+        central_directory.extend(20u16.to_le_bytes()); // version made by
+        central_directory.extend(20u16.to_le_bytes()); // version needed
+        central_directory.extend(0u16.to_le_bytes()); // flags
+        central_directory.extend(0u16.to_le_bytes()); // method: stored
+        central_directory.extend(0u16.to_le_bytes()); // time
+        central_directory.extend(DOS_DATE.to_le_bytes());
+        central_directory.extend(crc.to_le_bytes());
+        central_directory.extend(size(data.len())?.to_le_bytes());
+        central_directory.extend(size(data.len())?.to_le_bytes());
+        central_directory.extend(name_len.to_le_bytes());
+        central_directory.extend([0u8; 12]); // extra, comment, disk, attributes
+        central_directory.extend(offset.to_le_bytes());
+        central_directory.extend(name.as_bytes());
+    }
+
+    // End of central directory record
+    let directory_offset = size(archive.len())?;
+    let directory_size = size(central_directory.len())?;
+    archive.extend(central_directory);
+    archive.extend(0x0605_4b50u32.to_le_bytes());
+    archive.extend([0u8; 4]); // disk numbers
+    archive.extend(entries.to_le_bytes());
+    archive.extend(entries.to_le_bytes());
+    archive.extend(directory_size.to_le_bytes());
+    archive.extend(directory_offset.to_le_bytes());
+    archive.extend(0u16.to_le_bytes()); // comment length
+
+    fs::write(zip_path, archive)
+}
+
+/// CRC-32 (IEEE), as zip entries require
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// File written next to an HTML page, e.g. ("index.html", "config.json") -> "index.config.json"
+fn sidecar_file_name(html_name: &str, suffix: &str) -> String {
+    let stem = Path::new(html_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(html_name);
+    format!("{}.{}", stem, suffix)
+}
+
+fn generate_ui_generator_code(package_name: &str, functions: &[BoundFunction], options: &Options) -> String {
     let mut code = String::new();
 
     // Add imports
@@ -292,24 +586,100 @@ fn generate_ui_generator_code(package_name: &str, functions: &[BoundFunction]) -
     code.push_str("    fs::create_dir_all(\"pkg\")\n");
     code.push_str("        .expect(\"Failed to create pkg directory\");\n\n");
 
+    if options.external_css {
+        // Written once and linked from every page, so browsers cache it
+        code.push_str(&format!("    fs::write(\"pkg/{}\", clap_web_code_gen::CLI_UI_CSS)\n", CSS_FILE_NAME));
+        code.push_str("        .expect(\"Failed to write stylesheet\");\n");
+        code.push_str(&format!("    println!(\"  Generated: pkg/{}\");\n\n", CSS_FILE_NAME));
+    }
+    if options.external_scripts() {
+        // The scripts every page shares, also written once
+        for (file, script) in [
+            (I18N_FILE_NAME, "clap_web_code_gen::I18N_JS"),
+            (ARG_BUILDER_FILE_NAME, "clap_web_code_gen::arg_builder_js()"),
+        ] {
+            code.push_str(&format!("    fs::write(\"pkg/{}\", {})\n", file, script));
+            code.push_str("        .expect(\"Failed to write script\");\n");
+            code.push_str(&format!("    println!(\"  Generated: pkg/{}\");\n", file));
+        }
+        code.push('\n');
+    }
+
     // Convert package name to valid Rust identifier (hyphens -> underscores)
     let rust_package_name = package_name.replace('-', "_");
 
     // Generate code for each function
     for func in functions {
-        let ui_gen_fn = format!("generate_{}_ui", func.name);
         let output_file = format!("pkg/{}", func.html_name);
 
-        // Build fully qualified function path
-        let full_fn_path = if func.module_path.is_empty() {
-            format!("{}::{}", rust_package_name, ui_gen_fn)
-        } else {
-            format!("{}::{}::{}", rust_package_name, func.module_path, ui_gen_fn)
+        // Build fully qualified paths of the generated functions
+        let full_fn_path = |kind: &str| {
+            let gen_fn = format!("generate_{}_{}", func.name, kind);
+            if func.module_path.is_empty() {
+                format!("{}::{}", rust_package_name, gen_fn)
+            } else {
+                format!("{}::{}::{}", rust_package_name, func.module_path, gen_fn)
+            }
         };
 
         code.push_str(&format!("    // Generate UI for {}\n", func.name));
-        code.push_str(&format!("    let html = {}(\"{}\", \"\");\n",
-            full_fn_path, package_name));
+        if options.json_schema {
+            let schema_file = sidecar_file_name(&func.html_name, "schema.json");
+            code.push_str(&format!("    fs::write(\"pkg/{}\", {}())\n", schema_file, full_fn_path("schema")));
+            code.push_str("        .expect(\"Failed to write schema file\");\n");
+            code.push_str(&format!("    println!(\"  Generated: pkg/{}\");\n", schema_file));
+        }
+        // The config is only adjusted for the options below
+        let binding = if options.external_config || options.config_module || options.external_css {
+            "let mut config"
+        } else {
+            "let config"
+        };
+        code.push_str(&format!("    {} = {}(\"{}\", \"\");\n",
+            binding, full_fn_path("config"), package_name));
+        if options.external_css {
+            code.push_str(&format!("    config.external_css = Some(\"./{}\".to_string());\n", CSS_FILE_NAME));
+        }
+        // Problems that break parts of the page; it's still written, so the
+        // other pages and files are generated as usual
+        code.push_str("    if let Err(errors) = config.validate() {\n");
+        code.push_str(&format!("        eprintln!(\"  Invalid config for {}:\");\n", func.name));
+        code.push_str("        for error in errors {\n");
+        code.push_str("            eprintln!(\"    {}\", error);\n");
+        code.push_str("        }\n");
+        code.push_str("    }\n");
+        if options.config_module {
+            // The page imports its config from a module next to it
+            let module_file = sidecar_file_name(&func.html_name, "config.js");
+            code.push_str(&format!("    config.config_module = Some(\"./{}\".to_string());\n", module_file));
+            code.push_str(&format!("    fs::write(\"pkg/{}\", clap_web_code_gen::config_to_js_module(&config))\n", module_file));
+            code.push_str("        .expect(\"Failed to write config module\");\n");
+            code.push_str(&format!("    println!(\"  Generated: pkg/{}\");\n", module_file));
+        } else if options.external_config {
+            // The page fetches its config from a sidecar file next to it
+            let config_file = sidecar_file_name(&func.html_name, "config.json");
+            code.push_str(&format!("    config.external_config = Some(\"./{}\".to_string());\n", config_file));
+            code.push_str(&format!("    fs::write(\"pkg/{}\", clap_web_code_gen::config_to_json(&config))\n", config_file));
+            code.push_str("        .expect(\"Failed to write config file\");\n");
+            code.push_str(&format!("    println!(\"  Generated: pkg/{}\");\n", config_file));
+        }
+        if options.external_scripts() {
+            // The page's own script goes next to it, the shared ones were written above
+            let script_file = sidecar_file_name(&func.html_name, "ui.js");
+            code.push_str("    config.external_scripts = Some(clap_web_code_gen::ExternalScripts {\n");
+            code.push_str(&format!("        i18n: \"./{}\".to_string(),\n", I18N_FILE_NAME));
+            code.push_str(&format!("        arg_builder: \"./{}\".to_string(),\n", ARG_BUILDER_FILE_NAME));
+            code.push_str(&format!("        page: \"./{}\".to_string(),\n", script_file));
+            code.push_str("    });\n");
+            code.push_str(&format!("    fs::write(\"pkg/{}\", clap_web_code_gen::page_script(&config))\n", script_file));
+            code.push_str("        .expect(\"Failed to write script\");\n");
+            code.push_str(&format!("    println!(\"  Generated: pkg/{}\");\n", script_file));
+        }
+        // Parts of the CLI the page can't represent, e.g. non-UTF-8 defaults
+        code.push_str("    for warning in config.warnings() {\n");
+        code.push_str("        eprintln!(\"  Warning: {}\", warning);\n");
+        code.push_str("    }\n");
+        code.push_str("    let html = clap_web_code_gen::generate_wasm_function_page(&config);\n");
         code.push_str(&format!("    fs::write(\"{}\", html)\n", output_file));
         code.push_str("        .expect(\"Failed to write HTML file\");\n");
         code.push_str(&format!("    println!(\"  Generated: {}\");\n\n", output_file));
@@ -326,13 +696,13 @@ fn get_package_name(project_root: &Path) -> String {
     if let Ok(content) = fs::read_to_string(cargo_toml) {
         for line in content.lines() {
             let line = line.trim();
-            if line.starts_with("name") {
-                if let Some(eq_pos) = line.find('=') {
-                    let value = line[eq_pos + 1..].trim();
-                    // Remove quotes
-                    let name = value.trim_matches('"').trim_matches('\'');
-                    return name.to_string();
-                }
+            if line.starts_with("name")
+                && let Some(eq_pos) = line.find('=')
+            {
+                let value = line[eq_pos + 1..].trim();
+                // Remove quotes
+                let name = value.trim_matches('"').trim_matches('\'');
+                return name.to_string();
             }
         }
     }
@@ -340,7 +710,7 @@ fn get_package_name(project_root: &Path) -> String {
     "unknown".to_string()
 }
 
-fn create_temp_manifest(gen_dir: &Path, package_name: &str, project_root: &Path) -> PathBuf {
+fn create_temp_manifest(gen_dir: &Path, package_name: &str, project_root: &Path) -> Result<PathBuf, GenError> {
     // Find the clap_web_code_gen dependency from the user's Cargo.toml
     let clap_web_code_gen_dep = find_clap_web_code_gen_dependency(project_root);
 
@@ -367,10 +737,12 @@ clap_web_code_gen = {}
     );
 
     let manifest_path = gen_dir.join("Cargo.toml");
-    fs::write(&manifest_path, manifest_content)
-        .expect("Failed to write temporary Cargo.toml");
+    fs::write(&manifest_path, manifest_content).map_err(|source| GenError::Io {
+        context: "Failed to write temporary Cargo.toml",
+        source,
+    })?;
 
-    manifest_path
+    Ok(manifest_path)
 }
 
 fn find_clap_web_code_gen_dependency(project_root: &Path) -> String {
@@ -396,17 +768,17 @@ fn find_clap_web_code_gen_dependency(project_root: &Path) -> String {
             }
 
             // Look for clap_web_code_gen dependency (or code_gen as a renamed dep)
-            if in_dependencies && (trimmed.starts_with("clap_web_code_gen") || trimmed.starts_with("code_gen")) {
-                if let Some(eq_pos) = trimmed.find('=') {
-                    let dep_spec = trimmed[eq_pos + 1..].trim();
-
-                    // If it's a path dependency, resolve to absolute path
-                    if dep_spec.contains("path") {
-                        return resolve_path_dependency(dep_spec, project_root);
-                    }
-
-                    return dep_spec.to_string();
+            if in_dependencies && (trimmed.starts_with("clap_web_code_gen") || trimmed.starts_with("code_gen"))
+                && let Some(eq_pos) = trimmed.find('=')
+            {
+                let dep_spec = trimmed[eq_pos + 1..].trim();
+
+                // If it's a path dependency, resolve to absolute path
+                if dep_spec.contains("path") {
+                    return resolve_path_dependency(dep_spec, project_root);
                 }
+
+                return dep_spec.to_string();
             }
         }
     }
@@ -454,3 +826,195 @@ fn resolve_path_dependency(dep_spec: &str, project_root: &Path) -> String {
     // If we can't parse it, return as-is
     dep_spec.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bound(name: &str, module_path: &str, html_name: &str) -> BoundFunction {
+        BoundFunction {
+            name: name.to_string(),
+            module_path: module_path.to_string(),
+            html_name: html_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_extract_and_validate_bound_functions() {
+        let ast = syn::parse_file(r#"
+            #[web_ui_bind]
+            pub fn run(args: Cli) {}
+
+            #[web_ui_bind(html_name = "other.html")]
+            pub fn other(args: Cli) {}
+
+            pub fn unbound() {}
+        "#).unwrap();
+        let functions = extract_web_ui_bind_functions(&ast, "commands");
+        let summary: Vec<_> = functions.iter()
+            .map(|f| (f.name.as_str(), f.module_path.as_str(), f.html_name.as_str()))
+            .collect();
+        assert_eq!(summary, [("run", "commands", "index.html"), ("other", "commands", "other.html")]);
+
+        assert!(check_functions_not_in_main(&functions, "example").is_ok());
+        assert!(check_html_name_conflicts(&functions).is_ok());
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let in_main = [bound("run", "__BINARY_TARGET__", "index.html")];
+        match check_functions_not_in_main(&in_main, "example") {
+            Err(GenError::FunctionsInMain { functions, .. }) => assert_eq!(functions, ["run"]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let conflicting = [bound("a", "", "index.html"), bound("b", "cmd", "index.html")];
+        match check_html_name_conflicts(&conflicting) {
+            Err(GenError::HtmlNameConflict { html_name, first, second }) => {
+                assert_eq!((html_name.as_str(), first.as_str(), second.as_str()), ("index.html", "a", "b"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_external_config_generator_code() {
+        let functions = [bound("run", "commands", "tool.html")];
+        assert_eq!(sidecar_file_name("tool.html", "config.json"), "tool.config.json");
+
+        let inline = generate_ui_generator_code("my-tool", &functions, &Options::default());
+        assert!(inline.contains("let config = my_tool::commands::generate_run_config(\"my-tool\", \"\");"));
+        assert!(!inline.contains("config.json"));
+        assert!(inline.contains("    for warning in config.warnings() {\n        eprintln!(\"  Warning: {}\", warning);"));
+        assert!(inline.contains("    if let Err(errors) = config.validate() {\n        eprintln!(\"  Invalid config for run:\");"));
+        assert!(!inline.contains("exit("));
+
+        let options = Options { external_config: true, ..Default::default() };
+        let external = generate_ui_generator_code("my-tool", &functions, &options);
+        assert!(external.contains("let mut config = my_tool::commands::generate_run_config(\"my-tool\", \"\");"));
+        assert!(external.contains("config.external_config = Some(\"./tool.config.json\".to_string());"));
+        assert!(external.contains("fs::write(\"pkg/tool.config.json\", clap_web_code_gen::config_to_json(&config))"));
+        assert!(external.contains("fs::write(\"pkg/tool.html\", html)"));
+    }
+
+    #[test]
+    fn test_config_module_generator_code() {
+        let functions = [bound("run", "commands", "tool.html")];
+        assert!(!generate_ui_generator_code("my-tool", &functions, &Options::default()).contains("config_module"));
+
+        // The module replaces the JSON sidecar
+        let options = Options { config_module: true, external_config: true, ..Default::default() };
+        let code = generate_ui_generator_code("my-tool", &functions, &options);
+        assert!(code.contains("let mut config = my_tool::commands::generate_run_config(\"my-tool\", \"\");"));
+        assert!(code.contains("config.config_module = Some(\"./tool.config.js\".to_string());"));
+        assert!(code.contains("fs::write(\"pkg/tool.config.js\", clap_web_code_gen::config_to_js_module(&config))"));
+        assert!(!code.contains("config.json"));
+        assert_eq!(function_output_files(&functions[0], &options), ["tool.html", "tool.config.js", "tool.ui.js"]);
+    }
+
+    #[test]
+    fn test_json_schema_generator_code() {
+        let functions = [bound("run", "", "index.html")];
+        assert!(!generate_ui_generator_code("tool", &functions, &Options::default()).contains("schema"));
+
+        let options = Options { json_schema: true, ..Default::default() };
+        let code = generate_ui_generator_code("tool", &functions, &options);
+        assert!(code.contains("fs::write(\"pkg/index.schema.json\", tool::generate_run_schema())"));
+        assert!(code.contains("let config = tool::generate_run_config(\"tool\", \"\");"));
+    }
+
+    #[test]
+    fn test_external_css_generator_code() {
+        let functions = [bound("run", "", "index.html"), bound("check", "cmds", "check.html")];
+        assert!(!generate_ui_generator_code("tool", &functions, &Options::default()).contains("CLI_UI_CSS"));
+
+        let options = Options { external_css: true, ..Default::default() };
+        let code = generate_ui_generator_code("tool", &functions, &options);
+        // One shared stylesheet, linked from each page
+        assert_eq!(code.matches("fs::write(\"pkg/cli-ui.css\", clap_web_code_gen::CLI_UI_CSS)").count(), 1);
+        assert_eq!(code.matches("config.external_css = Some(\"./cli-ui.css\".to_string());").count(), 2);
+        assert!(code.contains("let mut config = tool::cmds::generate_check_config(\"tool\", \"\");"));
+        assert!(!code.contains("external_config"));
+    }
+
+    #[test]
+    fn test_external_scripts_generator_code() {
+        let functions = [bound("run", "", "index.html"), bound("check", "cmds", "check.html")];
+        assert!(!generate_ui_generator_code("tool", &functions, &Options::default()).contains("external_scripts"));
+
+        let options = Options { external_config: true, ..Default::default() };
+        let code = generate_ui_generator_code("tool", &functions, &options);
+        // The shared scripts are written once, each page's own next to it
+        assert_eq!(code.matches("fs::write(\"pkg/i18n.js\", clap_web_code_gen::I18N_JS)").count(), 1);
+        assert_eq!(code.matches("fs::write(\"pkg/arg-builder.js\", clap_web_code_gen::arg_builder_js())").count(), 1);
+        assert!(code.contains("        page: \"./check.ui.js\".to_string(),\n"));
+        assert!(code.contains("fs::write(\"pkg/check.ui.js\", clap_web_code_gen::page_script(&config))"));
+        assert_eq!(code.matches("config.external_scripts = Some(").count(), 2);
+        assert_eq!(function_output_files(&functions[1], &options), ["check.html", "check.config.json", "check.ui.js"]);
+    }
+
+    #[test]
+    fn test_zip_option() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(Options::parse(&args(&["clap-web-gen"])).unwrap().zip, None);
+        let options = Options::parse(&args(&["clap-web-gen", "--zip", "site.zip", "--json-schema"])).unwrap();
+        assert_eq!(options.zip, Some(PathBuf::from("site.zip")));
+        assert!(options.json_schema);
+        assert!(matches!(Options::parse(&args(&["clap-web-gen", "--zip"])), Err(GenError::MissingValue("--zip"))));
+    }
+
+    #[test]
+    fn test_zip_file_names() {
+        let dir = std::env::temp_dir().join(format!("clap-web-gen-zip-names-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("my_tool.js"), "").unwrap();
+
+        let functions = [bound("run", "", "index.html"), bound("check", "", "check.html")];
+        let options = Options { external_config: true, external_css: true, ..Default::default() };
+        // The wasm binary hasn't been built, so it's left out
+        assert_eq!(zip_file_names("my-tool", &functions, &options, &dir), [
+            "index.html", "index.config.json", "index.ui.js", "check.html", "check.config.json", "check.ui.js",
+            "cli-ui.css", "i18n.js", "arg-builder.js", "my_tool.js",
+        ]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_zip() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let dir = std::env::temp_dir().join(format!("clap-web-gen-zip-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("index.html"), "<html></html>").unwrap();
+        fs::write(dir.join("cli-ui.css"), "body {}").unwrap();
+
+        let zip_path = dir.join("site.zip");
+        write_zip(&zip_path, &dir, &["index.html".to_string(), "cli-ui.css".to_string()]).unwrap();
+        let zip = fs::read(&zip_path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        // Stored entries keep their content as-is, after a local header
+        assert_eq!(zip[..4], [0x50, 0x4b, 0x03, 0x04]);
+        assert_eq!(zip[30..40], *b"index.html");
+        assert_eq!(zip[40..53], *b"<html></html>");
+        // The end record lists both entries
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(end[..4], [0x50, 0x4b, 0x05, 0x06]);
+        assert_eq!(end[10..12], 2u16.to_le_bytes());
+
+        // Counts that don't fit the format's 16-bit fields are errors
+        let too_many = vec!["a".to_string(); 65_536];
+        assert_eq!(write_zip(&zip_path, &dir, &too_many).unwrap_err().to_string(), "file list too long for a zip archive");
+        let long_name = ["a".repeat(65_536)];
+        assert_eq!(write_zip(&zip_path, &dir, &long_name).unwrap_err().to_string(), "file name too long for a zip archive");
+    }
+
+    #[test]
+    fn test_calculate_module_path() {
+        let src = Path::new("/project/src");
+        assert_eq!(calculate_module_path(&src.join("lib.rs"), src), "");
+        assert_eq!(calculate_module_path(&src.join("main.rs"), src), "__BINARY_TARGET__");
+        assert_eq!(calculate_module_path(&src.join("commands/mod.rs"), src), "commands");
+        assert_eq!(calculate_module_path(&src.join("commands/run.rs"), src), "commands::run");
+    }
+}
@@ -1,29 +1,85 @@
 // ============================================================================
 // WASM Module Initialization
 // ============================================================================
-import init, { [FUNCTION_NAME] } from '[IMPORT_PATH]';
+// The module is imported dynamically so a missing or broken build shows a
+// message in the page instead of failing the whole script
+const WASM_IMPORT_PATH = '[IMPORT_PATH]';
+// Set when the config is fetched from a sidecar file instead of being inlined
+// as window.CLI_CONFIG (external_config)
+const CONFIG_PATH = '[CONFIG_PATH]';
+// Set when the config is imported from an ES module exporting CLI_CONFIG
+// instead (config_module)
+const CONFIG_MODULE = '[CONFIG_MODULE]';
 
 let wasmReady = false;
-const FIELDS = window.CLI_CONFIG.fields;
-const SUBCOMMANDS = window.CLI_CONFIG.subcommands || [];
-const form = document.getElementById('cliForm');
-const output = document.getElementById('output');
-const runButton = document.getElementById('runButton');
-const wasmFunction = [FUNCTION_NAME];
+let wasmFunction = null;
+// The page, or the shadow root when rendered as a web component
+const uiRoot = window.CLI_ROOT || document;
+const CONFIG = window.CLI_CONFIG || await loadConfig();
+const FIELDS = CONFIG.fields;
+const SUBCOMMANDS = CONFIG.subcommands || [];
+const form = uiRoot.getElementById('cliForm');
+const output = uiRoot.getElementById('output');
+const runButton = uiRoot.getElementById('runButton');
 let selectedSubcommand = null;
 
+// Reading field values and turning them into arguments is shared with other
+// frontends (arg-builder.js); these helpers are bound to this page's form
+const {
+    EXTERNAL_SUBCOMMAND, SHARED_FIELDS, fieldElementId, isNestedVecField, isCheckboxSetField,
+    getCheckboxSetBoxes, getCheckboxSetValues, getVecValues, getNestedVecValues, boolLiteral,
+    getFieldValues, positionalFields, flagFor, flagOccurrences, withTerminator, tokenizeCommandLine,
+} = window.createArgBuilder(CONFIG, form);
+
 function setStatus(message, type) {
-    const statusDiv = document.getElementById('status');
+    const statusDiv = uiRoot.getElementById('status');
     statusDiv.textContent = message;
     statusDiv.className = message ? `status ${type}` : '';
     statusDiv.style.display = message ? 'block' : 'none';
 }
 
+// Fetches the page config; the rest of the script waits for it
+async function loadConfig() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    try {
+        if (CONFIG_MODULE) {
+            return (await import(CONFIG_MODULE)).CLI_CONFIG;
+        }
+        const response = await fetch(CONFIG_PATH);
+        if (!response.ok) throw new Error(`HTTP ${response.status}`);
+        return await response.json();
+    } catch (e) {
+        setStatus(t('configLoadFailed').replace('{path}', CONFIG_MODULE || CONFIG_PATH), 'error');
+        throw e;
+    }
+}
+
 async function initWasm() {
     const t = window.i18n ? window.i18n.t : (key) => key;
     try {
         setStatus(t('loadingWasm'), 'loading');
-        await init();
+
+        let wasm;
+        if (CONFIG.external_wasm) {
+            // The host page has already loaded and initialized the module
+            // (or provides a promise of it)
+            wasm = await window.wasmModule;
+            if (!wasm || typeof wasm.[FUNCTION_NAME] !== 'function') {
+                setStatus(t('wasmModuleMissing').replace('{name}', '[FUNCTION_NAME]'), 'error');
+                return;
+            }
+        } else {
+            try {
+                wasm = await import(WASM_IMPORT_PATH);
+            } catch (e) {
+                setStatus(t('wasmImportFailed').replace('{path}', WASM_IMPORT_PATH), 'error');
+                console.error(`Failed to import ${WASM_IMPORT_PATH}:`, e);
+                return;
+            }
+            await wasm.default();
+        }
+
+        wasmFunction = wasm.[FUNCTION_NAME];
         wasmReady = true;
         setStatus(t('wasmLoaded'), 'success');
         setTimeout(() => setStatus('', ''), 2000);
@@ -36,8 +92,54 @@ async function initWasm() {
 // ============================================================================
 // Subcommand Handling
 // ============================================================================
+// With subcommand_style Tabs, the tablist stands in for the dropdown: it gets
+// a select-like `value` and fires `change` when a tab is picked, so the rest
+// of the page treats it like the select
+function initSubcommandTabs() {
+    const tablist = uiRoot.querySelector('.subcommand-tabs');
+    if (!tablist) return;
+    const tabs = Array.from(tablist.querySelectorAll('[role="tab"]'));
+    let value = '';
+
+    Object.defineProperty(tablist, 'value', {
+        get: () => value,
+        set: newValue => {
+            value = newValue;
+            const selected = tabs.find(tab => tab.dataset.subcommand === newValue);
+            tabs.forEach(tab => {
+                tab.setAttribute('aria-selected', tab === selected);
+                // Only one tab is in the tab order: the selected or the first
+                tab.tabIndex = tab === (selected || tabs[0]) ? 0 : -1;
+            });
+        },
+    });
+
+    const select = tab => {
+        if (tab.dataset.subcommand === value) return;
+        tablist.value = tab.dataset.subcommand;
+        tablist.dispatchEvent(new Event('change', { bubbles: true }));
+    };
+    tabs.forEach(tab => tab.addEventListener('click', () => select(tab)));
+
+    // Arrow keys, Home and End move to (and select) another tab
+    tablist.addEventListener('keydown', e => {
+        const index = tabs.indexOf(e.target);
+        if (index < 0) return;
+        const next = {
+            ArrowRight: tabs[(index + 1) % tabs.length],
+            ArrowLeft: tabs[(index - 1 + tabs.length) % tabs.length],
+            Home: tabs[0],
+            End: tabs[tabs.length - 1],
+        }[e.key];
+        if (!next) return;
+        e.preventDefault();
+        next.focus();
+        select(next);
+    });
+}
+
 function initSubcommandSelector() {
-    const selector = document.getElementById('subcommand-selector');
+    const selector = uiRoot.getElementById('subcommand-selector');
     if (!selector) return;
 
     selector.addEventListener('change', e => {
@@ -45,42 +147,363 @@ function initSubcommandSelector() {
         selectedSubcommand = newSubcommand || null;
 
         // Hide all subcommand field sections and disable required validation
-        document.querySelectorAll('.subcommand-fields').forEach(section => {
-            section.style.display = 'none';
+        uiRoot.querySelectorAll('.subcommand-fields').forEach(section => {
+            section.hidden = true;
             // Disable HTML5 validation on hidden fields
             section.querySelectorAll('input[required], select[required]').forEach(input => {
                 input.disabled = true;
             });
         });
 
-        // Show the selected subcommand's fields and enable validation
+        // Show the selected subcommand's fields, plus the shared ones, and
+        // enable validation
+        const shown = selectedSubcommand === EXTERNAL_SUBCOMMAND
+            ? [selectedSubcommand]
+            : [selectedSubcommand, SHARED_FIELDS];
         if (selectedSubcommand) {
-            const section = document.getElementById(`subcommand-${selectedSubcommand}`);
-            if (section) {
-                section.style.display = 'block';
+            shown.forEach(name => {
+                const section = uiRoot.getElementById(`subcommand-${name}`);
+                if (!section) return;
+                section.hidden = false;
                 // Re-enable HTML5 validation on visible fields
                 section.querySelectorAll('input, select').forEach(input => {
                     input.disabled = false;
                 });
-            }
+            });
         }
     });
 
     // Initialize: disable all subcommand fields initially
-    document.querySelectorAll('.subcommand-fields').forEach(section => {
+    uiRoot.querySelectorAll('.subcommand-fields').forEach(section => {
         section.querySelectorAll('input, select').forEach(input => {
             input.disabled = true;
         });
     });
 }
 
+// ============================================================================
+// Enum Buttons (radio-style alternative to a dropdown)
+// ============================================================================
+// Gives each button group the select-like properties the rest of the script
+// uses (value, defaultValue, options), so it's read and set like a dropdown
+function initEnumButtons() {
+    uiRoot.querySelectorAll('.enum-buttons').forEach(group => {
+        const radios = () => Array.from(group.querySelectorAll('input[type="radio"]'));
+        Object.defineProperties(group, {
+            value: {
+                get: () => radios().find(radio => radio.checked)?.value ?? '',
+                set: value => radios().forEach(radio => { radio.checked = radio.value === value; }),
+            },
+            defaultValue: {
+                get: () => radios().find(radio => radio.defaultChecked)?.value ?? '',
+            },
+            options: {
+                get: radios,
+            },
+        });
+    });
+}
+
+// ============================================================================
+// Integer Ranges (int_ranges: start and end inputs joined by a separator)
+// ============================================================================
+// Gives each range the properties the rest of the script uses for single-value
+// inputs (value, defaultValue, validity), so `start..end` is read and set as
+// one value. A range needs both ends or neither.
+function initIntRangeFields() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    uiRoot.querySelectorAll('.int-range').forEach(range => {
+        const separator = range.dataset.separator;
+        const [start, end] = range.querySelectorAll('input');
+        const join = (from, to) => (from && to ? `${from}${separator}${to}` : '');
+        const incomplete = () => Boolean(start.value.trim()) !== Boolean(end.value.trim());
+
+        Object.defineProperties(range, {
+            value: {
+                get: () => join(start.value.trim(), end.value.trim()),
+                set: value => {
+                    const index = value.indexOf(separator);
+                    start.value = index < 0 ? '' : value.slice(0, index);
+                    end.value = index < 0 ? '' : value.slice(index + separator.length);
+                },
+            },
+            defaultValue: {
+                get: () => join(start.defaultValue, end.defaultValue),
+            },
+            validity: {
+                get: () => ({ valid: start.validity.valid && end.validity.valid && !incomplete() }),
+            },
+            validationMessage: {
+                get: () => start.validationMessage || end.validationMessage || t('rangeIncomplete'),
+            },
+        });
+    });
+}
+
+// ============================================================================
+// Reset-to-Default Buttons (reset_buttons)
+// ============================================================================
+const resetButtonUpdaters = [];
+
+function refreshResetButtons() {
+    resetButtonUpdaters.forEach(update => update());
+}
+
+// Gives each single-value field with a default a "↺" button, shown only while
+// the field's value differs from the default; clicking it restores the default
+function initResetButtons() {
+    if (!CONFIG.reset_buttons) return;
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const sections = [{ prefix: '', fields: FIELDS }]
+        .concat(SUBCOMMANDS.map(sc => ({ prefix: sc.name, fields: sc.fields })));
+
+    sections.forEach(({ prefix, fields }) => {
+        fields.forEach(field => {
+            const element = uiRoot.getElementById(fieldElementId(prefix, field.name));
+            // Segmented toggles share one group, so they don't get a button;
+            // shared subcommand fields get one the first time they're seen
+            if (field.default_value === null || !element || field.field_type.type === 'Vec'
+                || isNestedVecField(field) || isCheckboxSetField(field) || element.closest('.segment')
+                || element.closest('.field-group').querySelector(':scope > .reset-default-btn')) return;
+
+            const defaultValue = field.default_value;
+            const isCheckbox = element.type === 'checkbox';
+            const matchesDefault = value => field.ignore_case
+                ? value.toLowerCase() === defaultValue.toLowerCase()
+                : value === defaultValue;
+
+            const button = document.createElement('button');
+            button.type = 'button';
+            button.className = 'reset-default-btn';
+            button.title = t('resetToDefault');
+            button.textContent = '↺';
+            button.hidden = true;
+            element.closest('.field-group').appendChild(button);
+
+            const update = () => {
+                button.hidden = isCheckbox
+                    ? element.checked === isTruthy(defaultValue)
+                    : matchesDefault(element.value);
+            };
+            button.addEventListener('click', () => {
+                if (isCheckbox) {
+                    element.checked = isTruthy(defaultValue);
+                } else {
+                    // Dropdowns and enum buttons take the option's own spelling
+                    const option = Array.from(element.options || []).find(o => matchesDefault(o.value));
+                    element.value = option ? option.value : defaultValue;
+                }
+                // Conditional defaults may apply to the field again
+                delete element.dataset.userEdited;
+                refreshConditionalDefaults();
+                refreshResetButtons();
+                refreshRequiredSummary();
+                clearFieldErrors(element.closest('.field-group'));
+                scheduleArgsBreakdown();
+            });
+            resetButtonUpdaters.push(update);
+            update();
+        });
+    });
+    ['input', 'change'].forEach(type => form.addEventListener(type, refreshResetButtons));
+}
+
+// ============================================================================
+// Pinned Fields (pinned_fields)
+// ============================================================================
+// Main command fields can be pinned to a section at the top of the form, with
+// the pins kept in localStorage. A pinned field is moved rather than copied,
+// so it's submitted as usual; a placeholder marks where it goes back to.
+const pinnedSection = uiRoot.getElementById('pinnedFields');
+const PINNED_STORAGE_KEY = `clap-web-gen-pinned:${location.pathname}:[FUNCTION_NAME]`;
+const pinPlaceholders = new Map();
+
+function setFieldPinned(group, pinned) {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const name = group.dataset.fieldName;
+    if (pinned && !pinPlaceholders.has(name)) {
+        const placeholder = document.createComment(`pinned: ${name}`);
+        group.before(placeholder);
+        pinPlaceholders.set(name, placeholder);
+        pinnedSection.appendChild(group);
+    } else if (!pinned && pinPlaceholders.has(name)) {
+        pinPlaceholders.get(name).replaceWith(group);
+        pinPlaceholders.delete(name);
+    }
+
+    const button = group.querySelector(':scope > .pin-field-btn');
+    button.setAttribute('aria-pressed', pinned);
+    button.title = t(pinned ? 'unpinField' : 'pinField');
+    button.setAttribute('aria-label', button.title);
+    pinnedSection.hidden = pinPlaceholders.size === 0;
+}
+
+function savePinnedFields() {
+    try {
+        localStorage.setItem(PINNED_STORAGE_KEY, JSON.stringify(Array.from(pinPlaceholders.keys())));
+    } catch (e) {
+        // Storage can be full or disabled; the pins still work until reload
+    }
+}
+
+function initPinnedFields() {
+    if (!pinnedSection) return;
+    // Subcommand fields stay in their section, which is shown and hidden as a whole
+    const groups = Array.from(form.querySelectorAll('.field-group[data-field-name]'))
+        .filter(group => !group.closest('.subcommand-fields'));
+
+    groups.forEach(group => {
+        const button = document.createElement('button');
+        button.type = 'button';
+        button.className = 'pin-field-btn';
+        button.textContent = '📌';
+        button.addEventListener('click', () => {
+            setFieldPinned(group, !pinPlaceholders.has(group.dataset.fieldName));
+            savePinnedFields();
+        });
+        group.prepend(button);
+        setFieldPinned(group, false);
+    });
+
+    let pinned = [];
+    try {
+        pinned = JSON.parse(localStorage.getItem(PINNED_STORAGE_KEY)) || [];
+    } catch (e) {
+        pinned = [];
+    }
+    pinned.forEach(name => {
+        const group = groups.find(g => g.dataset.fieldName === name);
+        if (group) setFieldPinned(group, true);
+    });
+}
+
+// ============================================================================
+// Breadcrumb (breadcrumb)
+// ============================================================================
+const breadcrumb = uiRoot.getElementById('breadcrumb');
+
+// The selected command path below the program: the subcommand, or the name
+// typed for an external one
+function selectedCommandPath() {
+    if (selectedSubcommand === EXTERNAL_SUBCOMMAND) {
+        const name = uiRoot.getElementById('external-subcommand-name').value.trim();
+        return name ? [name] : [];
+    }
+    return selectedSubcommand ? [selectedSubcommand] : [];
+}
+
+// Rebuilds the breadcrumb after the program name, which is rendered in the page
+function refreshBreadcrumb() {
+    if (!breadcrumb) return;
+    breadcrumb.querySelectorAll('.breadcrumb-subcommand').forEach(item => item.remove());
+    selectedCommandPath().forEach(name => {
+        const item = document.createElement('span');
+        item.className = 'breadcrumb-item breadcrumb-subcommand';
+        item.textContent = name;
+        breadcrumb.appendChild(item);
+    });
+}
+
+function initBreadcrumb() {
+    if (!breadcrumb) return;
+    const selector = uiRoot.getElementById('subcommand-selector');
+    if (selector) selector.addEventListener('change', refreshBreadcrumb);
+    const externalName = uiRoot.getElementById('external-subcommand-name');
+    if (externalName) externalName.addEventListener('input', refreshBreadcrumb);
+    refreshBreadcrumb();
+}
+
+// ============================================================================
+// Usage Line
+// ============================================================================
+const usage = uiRoot.getElementById('usage');
+
+// Shows the selected subcommand's usage, or the command's when there's none
+function refreshUsage() {
+    if (!usage) return;
+    const lines = Array.from(usage.querySelectorAll('.usage-line'));
+    const shown = lines.find(line => line.dataset.subcommand === selectedSubcommand)
+        || lines.find(line => line.dataset.subcommand === undefined);
+    lines.forEach(line => {
+        line.hidden = line !== shown;
+    });
+}
+
+function initUsage() {
+    if (!usage) return;
+    const selector = uiRoot.getElementById('subcommand-selector');
+    if (selector) selector.addEventListener('change', refreshUsage);
+    refreshUsage();
+}
+
+// ============================================================================
+// Required Fields Summary (required_summary)
+// ============================================================================
+const requiredSummary = uiRoot.getElementById('requiredSummary');
+
+// Shows how many of the required fields of the main command and the selected
+// subcommand are filled ("2/3"), and keeps Run disabled until all of them are
+function refreshRequiredSummary() {
+    if (!requiredSummary) return;
+    const sections = [{ prefix: '', fields: FIELDS }];
+    const subcommand = SUBCOMMANDS.find(sc => sc.name === selectedSubcommand);
+    if (subcommand) sections.push({ prefix: subcommand.name, fields: subcommand.fields });
+
+    let total = 0;
+    let filled = 0;
+    sections.forEach(({ prefix, fields }) => {
+        fields.filter(field => field.required).forEach(field => {
+            const elementId = fieldElementId(prefix, field.name);
+            // Checkboxes always have a value
+            if (!uiRoot.getElementById(elementId)
+                || (field.field_type.type === 'Bool' && !isCheckboxSetField(field))) return;
+            total++;
+            if (getFieldValues(field, elementId).length >= (field.min_values || 1)) filled++;
+        });
+    });
+
+    // The summary is inside the form, so only touch its text when it changes;
+    // otherwise the form's MutationObserver would keep calling back
+    const setCount = (selector, count) => {
+        const element = requiredSummary.querySelector(selector);
+        if (element.textContent !== String(count)) element.textContent = count;
+    };
+    setCount('.required-filled', filled);
+    setCount('.required-total', total);
+    requiredSummary.classList.toggle('complete', filled === total);
+    runButton.disabled = filled < total;
+}
+
+function initRequiredSummary() {
+    if (!requiredSummary) return;
+    ['input', 'change'].forEach(type => form.addEventListener(type, refreshRequiredSummary));
+    // List items and groups are added and removed without input events
+    new MutationObserver(refreshRequiredSummary).observe(form, { childList: true, subtree: true });
+    refreshRequiredSummary();
+}
+
+// ============================================================================
+// Path Fields (file picker filling in the file name)
+// ============================================================================
+function initPathFields() {
+    uiRoot.querySelectorAll('.path-file-input').forEach(picker => {
+        picker.addEventListener('change', () => {
+            const input = uiRoot.getElementById(picker.dataset.fieldName);
+            if (!picker.files.length || !input) return;
+            input.value = picker.files[0].name;
+            input.dataset.userEdited = 'true';
+            input.dispatchEvent(new Event('input', { bubbles: true }));
+        });
+    });
+}
+
 // ============================================================================
 // Vec Field Handling (dynamic tag input)
 // ============================================================================
 function initVecFields() {
-    document.querySelectorAll('.vec-input').forEach(input => {
+    uiRoot.querySelectorAll('.vec-input').forEach(input => {
         const fieldName = input.dataset.fieldName;
-        const itemsContainer = document.getElementById(`${fieldName}-items`);
+        const itemsContainer = uiRoot.getElementById(`${fieldName}-items`);
 
         input.addEventListener('keydown', e => {
             if (e.key === 'Enter' && input.value.trim()) {
@@ -106,91 +529,205 @@ function addVecItem(container, value) {
     container.appendChild(item);
 }
 
-function getVecValues(fieldName) {
-    const container = document.getElementById(`${fieldName}-items`);
-    return Array.from(container.children).map(item =>
-        item.textContent.slice(0, -1) // Remove the × character
-    );
+// ============================================================================
+// Nested Vec Field Handling (groups of values)
+// ============================================================================
+function initNestedVecFields() {
+    uiRoot.querySelectorAll('.add-group-btn').forEach(button => {
+        button.addEventListener('click', () => addNestedVecGroup(button.dataset.fieldName));
+    });
+}
+
+function addNestedVecGroup(fieldId, values = []) {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const groups = uiRoot.getElementById(`${fieldId}-groups`);
+
+    const group = document.createElement('div');
+    group.className = 'nested-vec-group';
+
+    const input = document.createElement('input');
+    input.type = 'text';
+    input.className = 'vec-input';
+    input.placeholder = t('enterValuePlaceholder');
+
+    const items = document.createElement('div');
+    items.className = 'vec-items';
+
+    const removeBtn = document.createElement('span');
+    removeBtn.className = 'vec-item-remove nested-vec-group-remove';
+    removeBtn.textContent = '×';
+    removeBtn.onclick = () => group.remove();
+
+    input.addEventListener('keydown', e => {
+        if (e.key === 'Enter' && input.value.trim()) {
+            e.preventDefault();
+            addVecItem(items, input.value.trim());
+            input.value = '';
+        }
+    });
+    values.forEach(value => addVecItem(items, value));
+
+    group.append(removeBtn, input, items);
+    groups.appendChild(group);
+    if (values.length === 0) input.focus();
+}
+
+// ============================================================================
+// Checkbox Sets (fixed options, one value per checked box)
+// ============================================================================
+function setCheckboxSetValues(fieldId, values) {
+    getCheckboxSetBoxes(fieldId).forEach(box => {
+        box.checked = values.includes(box.value);
+    });
 }
 
 // ============================================================================
 // Form Validation (using HTML5 + custom Vec validation)
 // ============================================================================
-function validateForm() {
-    const t = window.i18n ? window.i18n.t : (key) => key;
-    const errors = [];
+// Marks an invalid element and shows the message beneath its field group
+function markFieldError(element, message) {
+    element.classList.add('error');
+    const group = element.closest('.field-group');
+    if (!group) return;
+
+    let fieldError = group.querySelector(':scope > .field-error');
+    if (!fieldError) {
+        fieldError = document.createElement('div');
+        fieldError.className = 'field-error';
+        group.appendChild(fieldError);
+    }
+    fieldError.textContent = fieldError.textContent ? `${fieldError.textContent}; ${message}` : message;
+}
 
-    // Helper to check if an element is in a visible section
-    const isVisible = (element) => {
-        // Check if element is in a subcommand section
-        const subcommandSection = element.closest('.subcommand-fields');
-        if (subcommandSection) {
-            // Only validate if this subcommand section is visible
-            return subcommandSection.style.display !== 'none';
-        }
-        // Otherwise it's a main field, always validate
-        return true;
-    };
+// Removes the inline errors (and error styling) within a container
+function clearFieldErrors(container) {
+    container.querySelectorAll('.field-error').forEach(el => el.remove());
+    container.querySelectorAll('.error').forEach(el => el.classList.remove('error'));
+}
 
-    // Validate main command fields
-    FIELDS.forEach(field => {
-        const element = document.getElementById(field.name);
-        if (!element) return;
+// Scrolls the first invalid field into view and focuses it, first expanding
+// collapsed sections that contain invalid fields. List and checkbox-set
+// errors mark a container, so its first enabled control gets the focus.
+function focusFirstError() {
+    form.querySelectorAll('details .error').forEach(el => {
+        el.closest('details').open = true;
+    });
 
-        element.classList.remove('error');
+    const target = form.querySelector('.error');
+    if (!target) return;
+    const controls = 'input:not([disabled]), select:not([disabled]), textarea:not([disabled]), button:not([disabled])';
+    const control = target.matches(controls) ? target : target.querySelector(controls);
+    // Subcommand fields are revealed by now, but a control that's still not
+    // rendered can't take focus
+    if (control && control.getClientRects().length > 0) {
+        control.focus({ preventScroll: true });
+    }
+    (target.closest('.field-group') || target).scrollIntoView({ behavior: 'smooth', block: 'center' });
+}
 
-        // Check required text/number fields
-        if (field.required && field.field_type.type !== 'Bool' && field.field_type.type !== 'Vec') {
-            if (!element.value.trim()) {
-                const label = field.long || field.name;
-                errors.push(`Field "${label}": ${t('fieldRequired')}`);
-                element.classList.add('error');
-            }
+// Validates one list of fields (main command or the active subcommand)
+function validateFields(fields, prefix, errors) {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const errorTargets = new Map();
+
+    fields.forEach(field => {
+        const elementId = fieldElementId(prefix, field.name);
+        const element = uiRoot.getElementById(elementId);
+        if (!element || (field.field_type.type === 'Bool' && !isCheckboxSetField(field))) return;
+
+        // Vec fields (not supported by HTML5 validation) mark their container;
+        // checkbox sets mark the element holding the boxes
+        const isMulti = field.field_type.type === 'Vec' || isNestedVecField(field) || isCheckboxSetField(field);
+        const errorTarget = isMulti ? (uiRoot.getElementById(`${elementId}-container`) || element) : element;
+        const label = field.long || field.name;
+
+        errorTargets.set(field, errorTarget);
+        // A required field needs a value, and an open-ended num_args range
+        // (`2..`) needs at least its minimum once any value is given
+        const count = getFieldValues(field, elementId).length;
+        const minValues = field.min_values || 1;
+        if ((field.required || count > 0) && count < minValues) {
+            const message = minValues > 1
+                ? t('atLeastNValues').replace('{n}', minValues)
+                : t(isMulti ? 'atLeastOneValue' : 'fieldRequired');
+            errors.push(`Field "${label}": ${message}`);
+            markFieldError(errorTarget, message);
+        } else if (!isMulti && element.validity && !element.validity.valid) {
+            // Browser-level checks such as number ranges and steps
+            errors.push(`Field "${label}": ${element.validationMessage}`);
+            markFieldError(errorTarget, element.validationMessage);
         }
+    });
 
-        // Custom validation for Vec fields (not supported by HTML5)
-        if (field.field_type.type === 'Vec' && field.required) {
-            const values = getVecValues(field.name);
-            if (values.length === 0) {
-                const label = field.long || field.name;
-                errors.push(`Field "${label}": ${t('atLeastOneValue')}`);
-                const container = document.getElementById(`${field.name}-container`);
-                container.classList.add('error');
+    // Positionals are emitted by index, so an empty one can't be skipped
+    // while a later positional has a value. Values after `--` (last(true)
+    // positionals) don't need the earlier ones.
+    let emptyPositional = null;
+    positionalFields(fields).filter(field => !field.last).forEach(field => {
+        const errorTarget = errorTargets.get(field);
+        if (!errorTarget) return;
+        const elementId = fieldElementId(prefix, field.name);
+        if (getFieldValues(field, elementId).length === 0) {
+            emptyPositional = emptyPositional || { field, errorTarget };
+        } else if (emptyPositional) {
+            if (!emptyPositional.field.required) {
+                errors.push(`Field "${emptyPositional.field.name}": ${t('positionalGap')}`);
+                markFieldError(emptyPositional.errorTarget, t('positionalGap'));
             }
+            emptyPositional = null;
         }
     });
+}
+
+// Validates the form, marking each invalid field inline. Returns the messages.
+function validateForm() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const errors = [];
+    clearFieldErrors(form);
+
+    // Validate main command fields
+    validateFields(FIELDS, '', errors);
 
     // Validate selected subcommand fields (if any)
     if (selectedSubcommand) {
         const subcommandConfig = SUBCOMMANDS.find(sc => sc.name === selectedSubcommand);
         if (subcommandConfig) {
-            subcommandConfig.fields.forEach(field => {
-                const elementId = `${selectedSubcommand}-${field.name}`;
-                const element = document.getElementById(elementId);
-                if (!element) return;
-
-                element.classList.remove('error');
-
-                // Check required text/number fields
-                if (field.required && field.field_type.type !== 'Bool' && field.field_type.type !== 'Vec') {
-                    if (!element.value.trim()) {
-                        const label = field.long || field.name;
-                        errors.push(`Field "${label}": ${t('fieldRequired')}`);
-                        element.classList.add('error');
-                    }
-                }
+            validateFields(subcommandConfig.fields, selectedSubcommand, errors);
+        }
+    }
 
-                // Custom validation for Vec fields in subcommands
-                if (field.field_type.type === 'Vec' && field.required) {
-                    const values = getVecValues(elementId);
-                    if (values.length === 0) {
-                        const label = field.long || field.name;
-                        errors.push(`Field "${label}": ${t('atLeastOneValue')}`);
-                        const container = document.getElementById(`${elementId}-container`);
-                        container.classList.add('error');
-                    }
-                }
-            });
+    // With clap's subcommand_required, running without one is an error
+    if (CONFIG.subcommand_required && !selectedSubcommand) {
+        const selector = uiRoot.getElementById('subcommand-selector');
+        errors.push(`${t('subcommands')}: ${t('subcommandRequired')}`);
+        if (selector) markFieldError(selector, t('subcommandRequired'));
+    }
+
+    // An external subcommand needs a name, and its arguments must tokenize
+    if (selectedSubcommand === EXTERNAL_SUBCOMMAND) {
+        const name = uiRoot.getElementById('external-subcommand-name');
+        const externalArgs = uiRoot.getElementById('external-subcommand-args');
+
+        if (!name.value.trim() || name.value.trim().startsWith('-')) {
+            errors.push(`${t('externalSubcommandName')}: ${t('fieldRequired')}`);
+            markFieldError(name, t('fieldRequired'));
+        }
+        try {
+            tokenizeCommandLine(externalArgs.value);
+        } catch (e) {
+            errors.push(`${t('externalSubcommandArgs')}: ${e.message}`);
+            markFieldError(externalArgs, e.message);
+        }
+    }
+
+    // Extra arguments must tokenize cleanly (e.g. no unterminated quotes)
+    const extraArgs = uiRoot.getElementById('extra-args');
+    if (extraArgs) {
+        try {
+            tokenizeCommandLine(extraArgs.value);
+        } catch (e) {
+            errors.push(`${t('extraArgs')}: ${e.message}`);
+            markFieldError(extraArgs, e.message);
         }
     }
 
@@ -200,106 +737,101 @@ function validateForm() {
 // ============================================================================
 // CLI Argument Generation
 // ============================================================================
-function formToCliArgs() {
-    const args = [];
-    const positionalArgs = [];
-
-    // Process main command fields
-    FIELDS.forEach(field => {
-        const element = document.getElementById(field.name);
-        if (!element) return;
-
-        if (field.is_positional) {
-            // Store positional args for later (they go before subcommand)
-            const value = element.value.trim();
-            if (value) positionalArgs.push(value);
-        } else {
-            // Regular flag-based arguments
-            const flag = field.long ? `--${field.long}` : `-${field.short}`;
-            const fieldType = field.field_type.type;
-
-            switch (fieldType) {
-                case 'Bool':
-                    if (element.checked) args.push(flag);
-                    break;
+// Whether a value counts as true for clap's boolish parsers
+function isTruthy(value) {
+    return !['false', 'no', 'off', 'n', 'f', '0', ''].includes(String(value).toLowerCase());
+}
 
-                case 'Counter':
-                    const count = parseInt(element.value) || 0;
-                    for (let i = 0; i < count; i++) {
-                        args.push(flag);
-                    }
-                    break;
+// The same assembly other frontends get from window.buildCliArgs; extra
+// arguments are validated before Run, so tokenizing them doesn't throw
+function formToCliArgs() {
+    return window.buildCliArgs(CONFIG, form);
+}
 
-                case 'Vec':
-                    getVecValues(field.name).forEach(value => {
-                        args.push(flag, value);
-                    });
-                    break;
+// ============================================================================
+// Args Breakdown (flag / value / field table, updated live)
+// ============================================================================
+const breakdownTable = uiRoot.getElementById('argsBreakdown');
+let breakdownScheduled = false;
 
-                default: // String, Integer, Enum
-                    const value = element.value.trim();
-                    if (value) args.push(flag, value);
+// Rows of [flag, value, source] for the active args, in command line order
+function argsBreakdownRows() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const rows = [];
+
+    const addFieldRows = (fields, prefix) => {
+        const idFor = field => fieldElementId(prefix, field.name);
+        fields.forEach(field => {
+            if (field.is_positional || !uiRoot.getElementById(idFor(field))) return;
+            const occurrences = flagOccurrences(field, idFor(field));
+            if (field.field_type.type === 'Counter') {
+                // One row for the repeated flag
+                if (occurrences.length > 0) rows.push([flagFor(field), `×${occurrences.length}`, field.name]);
+                return;
             }
-        }
-    });
-
-    // Add positional args before subcommand
-    args.push(...positionalArgs);
+            const flag = field.require_equals ? `${flagFor(field)}=` : flagFor(field);
+            occurrences.forEach(values => rows.push([flag, values.join(' '), field.name]));
+        });
+        positionalFields(fields).forEach((field, i) => {
+            if (!uiRoot.getElementById(idFor(field))) return;
+            const values = withTerminator(field, getFieldValues(field, idFor(field)));
+            // Values of a last(true) positional follow a `--`
+            const position = field.last ? '--' : `#${field.index ?? i + 1}`;
+            if (values.length > 0) rows.push([position, values.join(' '), field.name]);
+        });
+    };
 
-    // Handle subcommand if one is selected
-    if (selectedSubcommand) {
-        args.push(selectedSubcommand);
+    addFieldRows(FIELDS, '');
 
-        // Find the subcommand config
+    if (selectedSubcommand === EXTERNAL_SUBCOMMAND) {
+        const name = uiRoot.getElementById('external-subcommand-name').value.trim();
+        const args = uiRoot.getElementById('external-subcommand-args').value.trim();
+        if (name) rows.push([name, args, t('externalSubcommand')]);
+    } else if (selectedSubcommand) {
+        rows.push([selectedSubcommand, '', t('subcommands')]);
         const subcommandConfig = SUBCOMMANDS.find(sc => sc.name === selectedSubcommand);
-        if (subcommandConfig) {
-            const subPositionalArgs = [];
+        if (subcommandConfig) addFieldRows(subcommandConfig.fields, selectedSubcommand);
+    }
 
-            subcommandConfig.fields.forEach(field => {
-                const elementId = `${selectedSubcommand}-${field.name}`;
-                const element = document.getElementById(elementId);
-                if (!element) return;
+    const extraArgs = uiRoot.getElementById('extra-args');
+    if (extraArgs && extraArgs.value.trim()) {
+        rows.push(['', extraArgs.value.trim(), t('extraArgs')]);
+    }
 
-                if (field.is_positional) {
-                    // Positional args for subcommand
-                    const value = element.value.trim();
-                    if (value) subPositionalArgs.push(value);
-                } else {
-                    // Regular flag-based arguments for subcommand
-                    const flag = field.long ? `--${field.long}` : `-${field.short}`;
-                    const fieldType = field.field_type.type;
-
-                    switch (fieldType) {
-                        case 'Bool':
-                            if (element.checked) args.push(flag);
-                            break;
-
-                        case 'Counter':
-                            const count = parseInt(element.value) || 0;
-                            for (let i = 0; i < count; i++) {
-                                args.push(flag);
-                            }
-                            break;
-
-                        case 'Vec':
-                            getVecValues(elementId).forEach(value => {
-                                args.push(flag, value);
-                            });
-                            break;
-
-                        default: // String, Integer, Enum
-                            const value = element.value.trim();
-                            if (value) args.push(flag, value);
-                    }
-                }
-            });
+    return rows;
+}
 
-            // Add subcommand positional args at the end
-            args.push(...subPositionalArgs);
-        }
+function refreshArgsBreakdown() {
+    if (!breakdownTable) return;
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const tbody = breakdownTable.tBodies[0];
+    tbody.innerHTML = '';
+
+    const rows = argsBreakdownRows();
+    if (rows.length === 0) {
+        const cell = tbody.insertRow().insertCell();
+        cell.colSpan = 3;
+        cell.className = 'args-breakdown-empty';
+        cell.setAttribute('data-i18n', 'noArgs');
+        cell.textContent = t('noArgs');
+        return;
     }
+    rows.forEach(([flag, value, source]) => {
+        const row = tbody.insertRow();
+        [flag, value, source].forEach(text => {
+            row.insertCell().textContent = text;
+        });
+    });
+}
 
-    return args;
+// Coalesces bursts of edits into one refresh per frame
+function scheduleArgsBreakdown() {
+    if (!breakdownTable || breakdownScheduled) return;
+    breakdownScheduled = true;
+    requestAnimationFrame(() => {
+        breakdownScheduled = false;
+        refreshArgsBreakdown();
+    });
 }
 
 // ============================================================================
@@ -439,10 +971,421 @@ function escapeHtml(text) {
     return div.innerHTML;
 }
 
+// ============================================================================
+// Command Line Parsing (fills the form from a command string)
+// ============================================================================
+function setFieldValue(field, elementId, value) {
+    const element = uiRoot.getElementById(elementId);
+    if (!element) return;
+
+    // An explicit value overrides conditional defaults
+    element.dataset.userEdited = 'true';
+
+    if (isNestedVecField(field)) {
+        const delimiter = CONFIG.nested_fields[field.name].delimiter;
+        addNestedVecGroup(elementId, delimiter ? value.split(delimiter) : [value]);
+        return;
+    }
+
+    if (isCheckboxSetField(field)) {
+        setCheckboxSetValues(elementId, getCheckboxSetValues(elementId).concat(value));
+        return;
+    }
+
+    switch (field.field_type.type) {
+        case 'Bool':
+            // Switches carry no value; value-taking bools are checked when truthy
+            element.checked = value === undefined || isTruthy(value);
+            break;
+        case 'Enum':
+            // Match the canonical option value when clap ignores case
+            // (fields with hidden values are plain text inputs)
+            const option = field.ignore_case && element.options
+                ? Array.from(element.options).find(o => o.value.toLowerCase() === value.toLowerCase())
+                : null;
+            element.value = option ? option.value : value;
+            break;
+        case 'Counter': {
+            const count = (parseInt(element.value) || 0) + 1;
+            // Level buttons (counter_levels) stop at the last level
+            element.value = String(element.options ? Math.min(count, element.options.length - 1) : count);
+            break;
+        }
+        case 'Vec':
+            addVecItem(uiRoot.getElementById(`${elementId}-items`), value);
+            break;
+        default:
+            element.value = value;
+    }
+}
+
+function findSubcommand(name) {
+    return SUBCOMMANDS.find(sc => sc.name === name || (sc.aliases || []).includes(name));
+}
+
+function selectSubcommand(name) {
+    const selector = uiRoot.getElementById('subcommand-selector');
+    if (!selector) return;
+    selector.value = name;
+    selector.dispatchEvent(new Event('change'));
+}
+
+// Fills a value_terminator field with the tokens from `start` up to its
+// terminator. Returns the index of the last token used (the terminator, when
+// there is one).
+function takeTerminatedValues(field, elementId, tokens, start) {
+    const end = tokens.indexOf(field.value_terminator, start);
+    const values = tokens.slice(start, end === -1 ? tokens.length : end);
+    values.forEach(value => setFieldValue(field, elementId, value));
+    return end === -1 ? tokens.length - 1 : end;
+}
+
+// Fills the form from a full command line (the first token is the program name).
+// Returns the tokens that couldn't be mapped to any field.
+function applyCommandLine(commandLine) {
+    const tokens = tokenizeCommandLine(commandLine).slice(1);
+    const unmapped = [];
+
+    clearForm();
+
+    let fields = FIELDS;
+    let prefix = '';
+    // A last(true) positional takes everything after `--`
+    let positionals = positionalFields(fields).filter(f => !f.last);
+    let lastPositional = fields.find(f => f.is_positional && f.last);
+    let onlyPositionals = false;
+
+    const idFor = field => fieldElementId(prefix, field.name);
+    const takesValue = field => field.field_type.type === 'Bool'
+        ? Boolean(field.bool_values)
+        : field.field_type.type !== 'Counter';
+
+    for (let i = 0; i < tokens.length; i++) {
+        const token = tokens[i];
+
+        if (!onlyPositionals && token === '--') {
+            onlyPositionals = true;
+        } else if (!onlyPositionals && token.startsWith('--')) {
+            const [name, inlineValue] = token.slice(2).split(/=(.*)/s);
+            const field = fields.find(f => f.long === name);
+            if (!field) {
+                unmapped.push(token);
+            } else if (!takesValue(field)) {
+                setFieldValue(field, idFor(field));
+            } else if (inlineValue !== undefined) {
+                setFieldValue(field, idFor(field), inlineValue);
+            } else if (field.value_terminator && i + 1 < tokens.length) {
+                i = takeTerminatedValues(field, idFor(field), tokens, i + 1);
+            } else if (i + 1 < tokens.length) {
+                setFieldValue(field, idFor(field), tokens[++i]);
+            } else {
+                unmapped.push(token);
+            }
+        } else if (!onlyPositionals && token.startsWith('-') && token.length > 1 && isNaN(token)) {
+            // Short flags may be clustered (-bcc) or carry a value (-sfoo / -s foo)
+            const chars = token.slice(1);
+            for (let j = 0; j < chars.length; j++) {
+                const field = fields.find(f => f.short === chars[j]);
+                if (!field) {
+                    unmapped.push(`-${chars[j]}`);
+                } else if (!takesValue(field)) {
+                    setFieldValue(field, idFor(field));
+                } else {
+                    const rest = chars.slice(j + 1).replace(/^=/, '');
+                    if (rest) {
+                        setFieldValue(field, idFor(field), rest);
+                    } else if (field.value_terminator && i + 1 < tokens.length) {
+                        i = takeTerminatedValues(field, idFor(field), tokens, i + 1);
+                    } else if (i + 1 < tokens.length) {
+                        setFieldValue(field, idFor(field), tokens[++i]);
+                    } else {
+                        unmapped.push(`-${chars[j]}`);
+                    }
+                    break;
+                }
+            }
+        } else if (!onlyPositionals && !prefix && findSubcommand(token)) {
+            // Aliases resolve to the canonical subcommand name
+            const subcommand = findSubcommand(token);
+            selectSubcommand(subcommand.name);
+            fields = subcommand.fields;
+            prefix = subcommand.name;
+            positionals = positionalFields(fields).filter(f => !f.last);
+            lastPositional = fields.find(f => f.is_positional && f.last);
+        } else if (onlyPositionals && lastPositional) {
+            setFieldValue(lastPositional, idFor(lastPositional), token);
+        } else if (positionals.length > 0) {
+            const field = positionals.shift();
+            if (field.value_terminator) {
+                i = takeTerminatedValues(field, idFor(field), tokens, i);
+            } else {
+                setFieldValue(field, idFor(field), token);
+            }
+        } else {
+            unmapped.push(token);
+        }
+    }
+
+    refreshConditionalDefaults();
+    refreshArgsBreakdown();
+    refreshResetButtons();
+    refreshRequiredSummary();
+    return unmapped;
+}
+
+// ============================================================================
+// Examples
+// ============================================================================
+function initExamples() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+
+    uiRoot.querySelectorAll('.example-chip').forEach(chip => {
+        chip.addEventListener('click', () => {
+            try {
+                const unmapped = applyCommandLine(chip.dataset.command);
+                if (unmapped.length > 0) {
+                    setStatus(t('unmappedArgs') + unmapped.join(' '), 'error');
+                }
+            } catch (e) {
+                setStatus(t('error') + ' ' + e.message, 'error');
+            }
+        });
+    });
+}
+
+// ============================================================================
+// Conditional Defaults (clap's default_value_if)
+// ============================================================================
+const conditionalDefaultUpdaters = [];
+
+function refreshConditionalDefaults() {
+    conditionalDefaultUpdaters.forEach(update => update());
+}
+
+// When a trigger field reaches its value, the dependent field takes the
+// conditional default, unless the user has edited it
+function initConditionalDefaults() {
+    const sections = [{ prefix: '', fields: FIELDS }]
+        .concat(SUBCOMMANDS.map(sc => ({ prefix: sc.name, fields: sc.fields })));
+
+    sections.forEach(({ prefix, fields }) => {
+        const idFor = name => fieldElementId(prefix, name);
+
+        fields.forEach(field => {
+            const rules = field.conditional_defaults || [];
+            const element = uiRoot.getElementById(idFor(field.name));
+            // Only single-value inputs can take a default
+            if (rules.length === 0 || !element || field.field_type.type === 'Vec'
+                || isNestedVecField(field) || isCheckboxSetField(field)) return;
+
+            const isCheckbox = element.type === 'checkbox';
+            const initialValue = isCheckbox ? String(element.checked) : element.value;
+            const markEdited = () => { element.dataset.userEdited = 'true'; };
+            element.addEventListener('input', markEdited);
+            element.addEventListener('change', markEdited);
+
+            const update = () => {
+                if (element.dataset.userEdited) return;
+
+                // Like clap, the first rule whose condition holds wins; a rule
+                // without a value only needs the trigger to be set
+                const rule = rules.find(r => {
+                    const trigger = uiRoot.getElementById(idFor(r.trigger));
+                    if (!trigger) return false;
+                    const current = trigger.type === 'checkbox'
+                        ? (trigger.checked ? 'true' : '')
+                        : trigger.value.trim();
+                    return r.value === null ? current !== '' : current === r.value;
+                });
+                const value = rule ? rule.default : initialValue;
+
+                if (isCheckbox) {
+                    element.checked = isTruthy(value);
+                } else {
+                    element.value = value;
+                }
+            };
+
+            rules.forEach(r => {
+                const trigger = uiRoot.getElementById(idFor(r.trigger));
+                if (!trigger) return;
+                trigger.addEventListener('input', update);
+                trigger.addEventListener('change', update);
+            });
+            conditionalDefaultUpdaters.push(update);
+            update();
+        });
+    });
+}
+
+// ============================================================================
+// JSON Tree Output (json_tree)
+// ============================================================================
+// A collapsible tree of output that is a JSON object or array, or null for any
+// other output. Nodes are <details>, so they toggle without script and still
+// work when the run is shown again from the history. Deeper levels start
+// collapsed.
+function renderJsonTree(text) {
+    let value;
+    try {
+        value = JSON.parse(text);
+    } catch (e) {
+        return null;
+    }
+    if (value === null || typeof value !== 'object') return null;
+    return `<div class="json-tree">${jsonTreeNode(null, value, 0)}</div>`;
+}
+
+function jsonTreeNode(key, value, depth) {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const label = key === null ? '' : `<span class="json-key">${escapeHtml(key)}</span>: `;
+    if (value === null || typeof value !== 'object') {
+        const type = value === null ? 'null' : typeof value;
+        return `<div class="json-leaf">${label}<span class="json-${type}">${escapeHtml(JSON.stringify(value))}</span></div>`;
+    }
+
+    const isArray = Array.isArray(value);
+    const [open, close] = isArray ? ['[', ']'] : ['{', '}'];
+    const entries = Object.entries(value);
+    if (entries.length === 0) return `<div class="json-leaf">${label}${open}${close}</div>`;
+
+    // Array items are labelled with their index
+    const children = entries
+        .map(([childKey, child]) => jsonTreeNode(isArray ? `${childKey}` : JSON.stringify(childKey), child, depth + 1))
+        .join('');
+    const count = `<span class="json-count">${escapeHtml(t('jsonItems').replace('{n}', entries.length))}</span>`;
+    return `<details class="json-node"${depth < 2 ? ' open' : ''}>`
+        + `<summary>${label}${open}${count}</summary>`
+        + `<div class="json-children">${children}</div>${close}</details>`;
+}
+
+function isJsonTree(html) {
+    return html.startsWith('<div class="json-tree">');
+}
+
+function setJsonTreeOpen(open) {
+    output.querySelectorAll('.json-tree details').forEach(node => {
+        node.open = open;
+    });
+}
+
 // ============================================================================
 // Main Function Execution
 // ============================================================================
-function runFunction() {
+// Labels the output pane with the subcommand that produced it ("Output (sub2):")
+function setOutputSubcommand(name) {
+    uiRoot.getElementById('output-subcommand').textContent = name ? ` (${name})` : '';
+}
+
+// Passes the assembled args through the page's window.transformArgs hook,
+// if one is defined; it receives a copy and returns the args to run with
+function transformArgs(args) {
+    if (typeof window.transformArgs !== 'function') return args;
+    const transformed = window.transformArgs(args.slice());
+    if (!Array.isArray(transformed)) {
+        throw new Error('window.transformArgs must return an array of arguments');
+    }
+    return transformed.map(String);
+}
+
+// Shows a run's output: replaces the pane, or with output_mode Append adds it
+// to the scrollback under a timestamped header
+function writeOutput(html, className, args) {
+    // Real output doesn't follow language changes like the placeholder does
+    output.removeAttribute('data-i18n');
+    if (CONFIG.output_line_numbers && !isJsonTree(html)) html = numberOutputLines(html);
+
+    if (CONFIG.output_mode !== 'Append') {
+        output.classList.remove('success', 'error');
+        output.classList.add(className);
+        output.innerHTML = html;
+        return;
+    }
+
+    // The first run replaces the "no output yet" placeholder
+    if (!output.querySelector('.output-run')) output.innerHTML = '';
+    const run = document.createElement('span');
+    run.className = `output-run ${className}`;
+    const header = document.createElement('span');
+    header.className = 'output-run-header';
+    header.textContent = `[${new Date().toLocaleTimeString()}] $ ${args.join(' ')}`;
+    const body = document.createElement('span');
+    body.innerHTML = html;
+    run.append(header, body);
+    output.appendChild(run);
+    output.scrollTop = output.scrollHeight;
+}
+
+// Wraps each output line in an .output-line span, which the stylesheet
+// numbers in a gutter (output_line_numbers). Each line keeps its line break,
+// so the pane's text is unchanged. Styled spans from parseAnsiColors aren't
+// nested, so one that runs across a line break is closed and reopened.
+function numberOutputLines(html) {
+    let open = '';
+    return html.replace(/\n$/, '').split('\n').map(line => {
+        line = open + line;
+        const lastOpen = line.lastIndexOf('<span');
+        open = lastOpen > line.lastIndexOf('</span>')
+            ? line.slice(lastOpen, line.indexOf('>', lastOpen) + 1)
+            : '';
+        return `<span class="output-line">${line}${open ? '</span>' : ''}\n</span>`;
+    }).join('');
+}
+
+// Empties the output pane (and the scrollback in append mode)
+function resetOutput() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    output.setAttribute('data-i18n', 'noOutputYet');
+    output.textContent = t('noOutputYet');
+    output.classList.remove('success', 'error');
+    setOutputSubcommand(null);
+}
+
+// Asks for confirmation in a modal dialog before a run (confirm_run);
+// resolves to true when the user confirms
+function confirmRun(message) {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+
+    return new Promise(resolve => {
+        const dialog = document.createElement('dialog');
+        dialog.className = 'confirm-dialog';
+        const text = document.createElement('p');
+        text.textContent = message;
+
+        const buttons = document.createElement('div');
+        buttons.className = 'button-group';
+        const confirmButton = document.createElement('button');
+        confirmButton.type = 'button';
+        confirmButton.className = 'confirm-btn';
+        confirmButton.textContent = t('confirmRun');
+        confirmButton.addEventListener('click', () => dialog.close('confirm'));
+        const cancelButton = document.createElement('button');
+        cancelButton.type = 'button';
+        cancelButton.className = 'cancel-btn';
+        cancelButton.textContent = t('cancel');
+        cancelButton.addEventListener('click', () => dialog.close('cancel'));
+        buttons.append(confirmButton, cancelButton);
+        dialog.append(text, buttons);
+
+        // Escape also closes the dialog, with an empty return value
+        dialog.addEventListener('close', () => {
+            dialog.remove();
+            resolve(dialog.returnValue === 'confirm');
+        });
+        form.after(dialog);
+        dialog.showModal();
+        // Cancelling is the safe default for a stray Enter
+        cancelButton.focus();
+    });
+}
+
+// Milliseconds for the timing status: one decimal for quick calls, whole
+// numbers otherwise
+function formatDuration(ms) {
+    return ms < 10 ? ms.toFixed(1) : String(Math.round(ms));
+}
+
+async function runFunction() {
     const t = window.i18n ? window.i18n.t : (key) => key;
 
     if (!wasmReady) {
@@ -450,100 +1393,396 @@ function runFunction() {
         return;
     }
 
-    // Clear previous error styling
-    form.querySelectorAll('.error').forEach(el => el.classList.remove('error'));
-
-    // Validate form
+    // Validation errors are shown inline beneath each field; the status line
+    // is kept for run-level errors
     const validationErrors = validateForm();
     if (validationErrors.length > 0) {
-        output.className = 'error';
-        output.textContent = t('validationError') + '\n' + validationErrors.join('\n');
-        setStatus(t('fixValidationErrors'), 'error');
-        output.scrollIntoView({ behavior: 'smooth', block: 'nearest' });
+        focusFirstError();
+        return;
+    }
+
+    if (CONFIG.confirm_run && !(await confirmRun(CONFIG.confirm_run))) {
         return;
     }
 
     // Build CLI arguments and execute
+    const state = getFormState();
+    let args = [];
+    // When the WASM call started, for show_timing
+    let started = null;
     try {
-        const args = formToCliArgs();
+        args = transformArgs(formToCliArgs());
         console.log('CLI args:', args);
+        setOutputSubcommand(selectedSubcommand === EXTERNAL_SUBCOMMAND
+            ? uiRoot.getElementById('external-subcommand-name').value.trim()
+            : selectedSubcommand);
 
         runButton.disabled = true;
         setStatus(t('running'), 'loading');
 
+        started = performance.now();
         const result = wasmFunction(args);
-
-        output.className = 'success';
+        const elapsed = performance.now() - started;
 
         // Parse ANSI color codes and display with proper formatting
+        let html;
         if (result !== undefined && result !== null) {
             const resultText = typeof result === 'string' ? result : JSON.stringify(result, null, 2);
-            output.innerHTML = parseAnsiColors(resultText);
+            html = (CONFIG.json_tree && renderJsonTree(resultText)) || parseAnsiColors(resultText);
         } else {
-            output.textContent = t('successNoReturn');
+            html = escapeHtml(t('successNoReturn'));
         }
+        writeOutput(html, 'success', args);
+        recordRun(args, html, 'success', state);
 
-        setStatus(t('success'), 'success');
-        setTimeout(() => setStatus('', ''), 2000);
+        if (CONFIG.show_timing) {
+            setStatus(t('completedIn').replace('{ms}', formatDuration(elapsed)), 'success');
+        } else {
+            setStatus(t('success'), 'success');
+            setTimeout(() => setStatus('', ''), 2000);
+        }
         output.scrollIntoView({ behavior: 'smooth', block: 'nearest' });
 
     } catch (e) {
-        output.className = 'error';
-        output.textContent = t('error') + '\n' + e;
-        setStatus(t('errorOccurred'), 'error');
+        const html = escapeHtml(t('error') + '\n' + e);
+        writeOutput(html, 'error', args);
+        recordRun(args, html, 'error', state);
+        if (CONFIG.show_timing && started !== null) {
+            setStatus(t('failedAfter').replace('{ms}', formatDuration(performance.now() - started)), 'error');
+        } else {
+            setStatus(t('errorOccurred'), 'error');
+        }
         output.scrollIntoView({ behavior: 'smooth', block: 'nearest' });
     } finally {
         runButton.disabled = false;
+        refreshRequiredSummary();
     }
 }
 
 // ============================================================================
-// Form Reset (using native HTML5 form.reset() + custom Vec cleanup)
+// Run History (previous runs, recalled from a dropdown)
 // ============================================================================
-function clearForm() {
+const historySelect = uiRoot.getElementById('runHistory');
+const restoreRunButton = uiRoot.getElementById('restoreRunButton');
+const HISTORY_STORAGE_KEY = `clap-web-gen-history:${location.pathname}:[FUNCTION_NAME]`;
+// Newest first: { time, args, html, className, state }
+let runHistory = [];
+
+// Adds a finished run, dropping the oldest beyond run_history_size
+function recordRun(args, html, className, state) {
+    if (!historySelect) return;
+    runHistory.unshift({ time: Date.now(), args, html, className, state });
+    runHistory = runHistory.slice(0, CONFIG.run_history_size ?? 5);
+    if (CONFIG.persist_run_history) {
+        try {
+            localStorage.setItem(HISTORY_STORAGE_KEY, JSON.stringify(runHistory));
+        } catch (e) {
+            // Storage can be full or disabled; the history still works in memory
+        }
+    }
+    renderRunHistory();
+}
+
+function renderRunHistory() {
     const t = window.i18n ? window.i18n.t : (key) => key;
+    historySelect.innerHTML = '';
+    const placeholder = new Option(t('previousRuns'), '');
+    placeholder.setAttribute('data-i18n', 'previousRuns');
+    historySelect.add(placeholder);
+    runHistory.forEach((entry, i) => {
+        const time = new Date(entry.time).toLocaleTimeString();
+        historySelect.add(new Option(`[${time}] $ ${entry.args.join(' ')}`, i));
+    });
+    restoreRunButton.disabled = true;
+}
+
+// Shows the chosen run's output in the pane
+function showHistoryEntry(entry) {
+    output.removeAttribute('data-i18n');
+    output.classList.remove('success', 'error');
+    output.classList.add(entry.className);
+    output.innerHTML = CONFIG.output_line_numbers && !isJsonTree(entry.html) ? numberOutputLines(entry.html) : entry.html;
+    setOutputSubcommand(entry.state.subcommand === EXTERNAL_SUBCOMMAND
+        ? entry.state.values['external-subcommand-name']
+        : entry.state.subcommand);
+}
+
+function initRunHistory() {
+    if (!historySelect) return;
+    if (CONFIG.persist_run_history) {
+        try {
+            runHistory = JSON.parse(localStorage.getItem(HISTORY_STORAGE_KEY)) || [];
+        } catch (e) {
+            runHistory = [];
+        }
+    }
+    renderRunHistory();
+
+    historySelect.addEventListener('change', () => {
+        const entry = runHistory[historySelect.value];
+        restoreRunButton.disabled = !entry;
+        if (entry) showHistoryEntry(entry);
+    });
+    restoreRunButton.addEventListener('click', () => {
+        const entry = runHistory[historySelect.value];
+        if (entry) setFormState(entry.state);
+    });
+}
+
+// Runs once after load (when enabled) if the populated form already validates,
+// e.g. when every required field has a default
+function autoRun() {
+    if (!CONFIG.auto_run || !wasmReady) return;
 
+    const valid = validateForm().length === 0;
+    // Don't greet the user with errors for a form they haven't touched
+    clearFieldErrors(form);
+    if (valid) runFunction();
+}
+
+// ============================================================================
+// Share Links (form state encoded in the URL fragment)
+// ============================================================================
+const STATE_PREFIX = '#state=';
+
+// Fields of the main command and the selected subcommand, with their element ids
+function activeFieldEntries() {
+    const entries = FIELDS.map(field => [field, field.name]);
+    const subcommandConfig = SUBCOMMANDS.find(sc => sc.name === selectedSubcommand);
+    if (subcommandConfig) {
+        subcommandConfig.fields.forEach(field => entries.push([field, fieldElementId(selectedSubcommand, field.name)]));
+    }
+    return entries;
+}
+
+// Raw text inputs that aren't fields (extra args, external subcommand)
+const STATE_TEXT_INPUTS = ['extra-args', 'external-subcommand-name', 'external-subcommand-args'];
+
+function getFormState() {
+    const values = {};
+    activeFieldEntries().forEach(([field, elementId]) => {
+        const element = uiRoot.getElementById(elementId);
+        if (!element) return;
+
+        if (isNestedVecField(field)) {
+            values[elementId] = getNestedVecValues(elementId);
+        } else if (isCheckboxSetField(field)) {
+            values[elementId] = getCheckboxSetValues(elementId);
+        } else if (field.field_type.type === 'Vec') {
+            values[elementId] = getVecValues(elementId);
+        } else if (field.field_type.type === 'Bool') {
+            values[elementId] = element.checked;
+        } else {
+            values[elementId] = element.value;
+        }
+    });
+    STATE_TEXT_INPUTS.forEach(id => {
+        const element = uiRoot.getElementById(id);
+        if (element && element.value) values[id] = element.value;
+    });
+    return { subcommand: selectedSubcommand, values };
+}
+
+function setFormState(state) {
+    clearForm();
+    if (state.subcommand) selectSubcommand(state.subcommand);
+
+    const values = state.values || {};
+    activeFieldEntries().forEach(([field, elementId]) => {
+        const element = uiRoot.getElementById(elementId);
+        const value = values[elementId];
+        if (!element || value === undefined) return;
+
+        // Shared values are explicit, so conditional defaults leave them alone
+        element.dataset.userEdited = 'true';
+        if (isNestedVecField(field)) {
+            value.forEach(group => addNestedVecGroup(elementId, group));
+        } else if (isCheckboxSetField(field)) {
+            setCheckboxSetValues(elementId, value);
+        } else if (field.field_type.type === 'Vec') {
+            value.forEach(item => addVecItem(uiRoot.getElementById(`${elementId}-items`), item));
+        } else if (field.field_type.type === 'Bool') {
+            element.checked = Boolean(value);
+        } else {
+            element.value = value;
+        }
+    });
+    STATE_TEXT_INPUTS.forEach(id => {
+        const element = uiRoot.getElementById(id);
+        if (element && values[id] !== undefined) element.value = values[id];
+    });
+    refreshConditionalDefaults();
+    refreshArgsBreakdown();
+    refreshResetButtons();
+    refreshRequiredSummary();
+    refreshBreadcrumb();
+    refreshUsage();
+}
+
+// URL-safe base64 of the UTF-8 JSON
+function encodeFormState(state) {
+    const bytes = new TextEncoder().encode(JSON.stringify(state));
+    const binary = Array.from(bytes, b => String.fromCharCode(b)).join('');
+    return btoa(binary).replace(/\+/g, '-').replace(/\//g, '_').replace(/=+$/, '');
+}
+
+function decodeFormState(encoded) {
+    const binary = atob(encoded.replace(/-/g, '+').replace(/_/g, '/'));
+    const bytes = Uint8Array.from(binary, c => c.charCodeAt(0));
+    return JSON.parse(new TextDecoder().decode(bytes));
+}
+
+async function shareFormState() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    const link = location.href.split('#')[0] + STATE_PREFIX + encodeFormState(getFormState());
+    history.replaceState(null, '', link);
+
+    try {
+        await navigator.clipboard.writeText(link);
+        setStatus(t('linkCopied'), 'success');
+        setTimeout(() => setStatus('', ''), 2000);
+    } catch (e) {
+        // Clipboard access can be denied (e.g. on insecure origins)
+        setStatus(t('shareLink') + link, 'success');
+    }
+}
+
+// Restores the form from a shared link, if the page was opened with one
+function restoreSharedState() {
+    const t = window.i18n ? window.i18n.t : (key) => key;
+    if (!CONFIG.share_button || !location.hash.startsWith(STATE_PREFIX)) return;
+
+    try {
+        setFormState(decodeFormState(location.hash.slice(STATE_PREFIX.length)));
+    } catch (e) {
+        setStatus(t('invalidSharedState') + e.message, 'error');
+    }
+}
+
+// ============================================================================
+// Form Reset (using native HTML5 form.reset() + custom Vec cleanup)
+// ============================================================================
+// Resets a single field to its default (or empty), from its "×" button
+function clearField(elementId) {
+    const element = uiRoot.getElementById(elementId);
+    if (!element) return;
+
+    const items = uiRoot.getElementById(`${elementId}-items`);
+    if (items) {
+        items.innerHTML = '';
+        element.value = '';
+    } else if (element.tagName === 'SELECT') {
+        const defaultOption = Array.from(element.options).find(o => o.defaultSelected);
+        element.value = defaultOption ? defaultOption.value : element.options[0]?.value ?? '';
+    } else {
+        element.value = element.defaultValue;
+    }
+
+    // Conditional defaults may apply to the field again
+    delete element.dataset.userEdited;
+    refreshConditionalDefaults();
+    refreshResetButtons();
+    refreshRequiredSummary();
+    clearFieldErrors(element.closest('.field-group'));
+}
+
+function clearForm() {
     form.reset();
 
     // Clear Vec field items (not handled by form.reset())
-    document.querySelectorAll('.vec-items').forEach(container => {
+    uiRoot.querySelectorAll('.vec-items, .nested-vec-groups').forEach(container => {
         container.innerHTML = '';
     });
 
     // Reset subcommand selection
     selectedSubcommand = null;
-    const selector = document.getElementById('subcommand-selector');
+    const selector = uiRoot.getElementById('subcommand-selector');
     if (selector) {
         selector.value = '';
+        refreshBreadcrumb();
+        refreshUsage();
         // Hide all subcommand field sections and disable validation
-        document.querySelectorAll('.subcommand-fields').forEach(section => {
-            section.style.display = 'none';
+        uiRoot.querySelectorAll('.subcommand-fields').forEach(section => {
+            section.hidden = true;
             section.querySelectorAll('input, select').forEach(input => {
                 input.disabled = true;
             });
         });
     }
 
-    // Reset output
-    output.textContent = t('noOutputYet');
-    output.className = '';
+    // Conditional defaults apply again to the reset form
+    form.querySelectorAll('[data-user-edited]').forEach(el => delete el.dataset.userEdited);
+    refreshConditionalDefaults();
+
+    // Reset output; the append-mode scrollback has its own Clear button
+    if (CONFIG.output_mode !== 'Append') {
+        resetOutput();
+    }
     setStatus('', '');
 
-    // Clear error styling
-    form.querySelectorAll('.error').forEach(el => el.classList.remove('error'));
+    // Clear inline errors
+    clearFieldErrors(form);
+    scheduleArgsBreakdown();
+    refreshResetButtons();
+    refreshRequiredSummary();
 }
 
 // ============================================================================
 // Initialization
 // ============================================================================
 runButton.addEventListener('click', runFunction);
-document.getElementById('clearButton').addEventListener('click', clearForm);
+uiRoot.getElementById('clearButton').addEventListener('click', clearForm);
+const shareButton = uiRoot.getElementById('shareButton');
+if (shareButton) shareButton.addEventListener('click', shareFormState);
+const expandAllButton = uiRoot.getElementById('expandAllButton');
+if (expandAllButton) expandAllButton.addEventListener('click', () => setJsonTreeOpen(true));
+const collapseAllButton = uiRoot.getElementById('collapseAllButton');
+if (collapseAllButton) collapseAllButton.addEventListener('click', () => setJsonTreeOpen(false));
+
+const clearOutputButton = uiRoot.getElementById('clearOutputButton');
+if (clearOutputButton) clearOutputButton.addEventListener('click', resetOutput);
+
+form.addEventListener('click', (e) => {
+    const button = e.target.closest('.clear-field-btn');
+    if (button) clearField(button.dataset.fieldName);
+});
+
+// Editing a field clears its inline error
+['input', 'change'].forEach(type => form.addEventListener(type, (e) => {
+    const group = e.target.closest('.field-group');
+    if (group) clearFieldErrors(group);
+}));
+
+// The args breakdown follows every edit; vec items and groups are added and
+// removed without input events, so watch the form's DOM as well
+if (breakdownTable) {
+    ['input', 'change'].forEach(type => form.addEventListener(type, scheduleArgsBreakdown));
+    new MutationObserver(scheduleArgsBreakdown).observe(form, { childList: true, subtree: true });
+}
 
 // Initialize i18n first
 if (window.i18n) {
     window.i18n.initI18n();
 }
 
-initWasm();
+const wasmInit = initWasm();
+initEnumButtons();
+initIntRangeFields();
+initPathFields();
 initVecFields();
+initNestedVecFields();
+initSubcommandTabs();
 initSubcommandSelector();
+initExamples();
+initConditionalDefaults();
+initResetButtons();
+initRunHistory();
+initPinnedFields();
+initRequiredSummary();
+initBreadcrumb();
+initUsage();
+restoreSharedState();
+refreshArgsBreakdown();
+
+// Auto-run waits for the form to be populated and WASM to be loaded
+wasmInit.then(autoRun);
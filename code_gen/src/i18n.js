@@ -8,36 +8,84 @@ const translations = {
         language: 'Language',
         run: 'Run',
         reset: 'Reset',
-        output: 'Output:',
+        share: 'Share',
+        linkCopied: 'Link copied to clipboard',
+        shareLink: 'Share this link: ',
+        invalidSharedState: 'Could not restore the shared form state: ',
+        output: 'Output',
         noOutputYet: 'No output yet. Fill in the form and click "Run".',
+        clearTerminal: 'Clear terminal',
+        expandAll: 'Expand all',
+        collapseAll: 'Collapse all',
+        jsonItems: '{n} items',
+        previousRuns: 'Previous runs',
+        pinnedFields: 'Pinned',
+        pinField: 'Pin to the top',
+        unpinField: 'Unpin',
+        sharedOptions: 'Options for every subcommand',
+        usage: 'Usage',
+        restoreForm: 'Restore form',
+        examples: 'Examples:',
+        confirmRun: 'Run',
+        cancel: 'Cancel',
+        argsBreakdown: 'Args breakdown',
+        argFlag: 'Flag',
+        argValue: 'Value',
+        argSource: 'Field',
+        noArgs: 'No arguments',
+        requiredFieldsFilled: 'required fields filled',
 
         // Subcommands
         subcommands: 'Subcommands',
         selectSubcommand: 'Select Subcommand',
         selectSubcommandPlaceholder: '-- Select a subcommand --',
+        chooseOneSubcommand: 'Choose one:',
+        subcommandRequired: 'Choose a subcommand',
         optionsFor: 'Options for',
 
         // Form fields
         selectOption: '-- Select an option --',
+        noneOption: 'None',
         enterValuePlaceholder: 'Enter value and press Enter',
+        pathPlaceholder: 'path/to/file',
+        rangeStart: 'Start',
+        rangeEnd: 'End',
+        nonUtf8Default: "Default can't be shown (not valid UTF-8)",
+        browse: 'Browse…',
+        addGroup: 'Add group',
         requiredField: 'Required field',
+        advancedOptions: 'Advanced options',
+        extraArgs: 'Extra arguments',
+        extraArgsHelp: 'Appended to the command line as-is; use quotes for values with spaces',
+        externalSubcommand: 'Other (custom)',
+        externalSubcommandName: 'Subcommand name',
+        externalSubcommandArgs: 'Arguments',
 
         // Status messages
         loadingWasm: 'Loading WASM module...',
         wasmLoaded: 'WASM module loaded successfully!',
         wasmLoadFailed: 'Failed to load WASM module: ',
+        wasmImportFailed: 'Failed to load WASM module at {path}; did you run wasm-pack build?',
+        wasmModuleMissing: 'window.wasmModule is not set or has no {name} function',
+        configLoadFailed: 'Failed to load page configuration from {path}',
         wasmNotReady: 'WASM module not ready yet. Please wait...',
         running: 'Running function...',
         success: 'Function executed successfully!',
         successNoReturn: 'Function executed successfully (no return value)',
         errorOccurred: 'Error occurred',
-        fixValidationErrors: 'Please fix validation errors',
-        validationError: 'Validation Error:',
+        completedIn: 'Completed in {ms} ms',
+        failedAfter: 'Failed after {ms} ms',
         error: 'Error:',
+        unmappedArgs: 'Some arguments could not be mapped to the form: ',
 
         // Validation messages
         fieldRequired: 'Required field is empty',
         atLeastOneValue: 'At least one value is required',
+        atLeastNValues: 'At least {n} values are required',
+        resetToDefault: 'Reset to default',
+        clearField: 'Clear',
+        positionalGap: 'Must be filled because a later positional argument is set',
+        rangeIncomplete: 'Enter both ends of the range, or neither',
 
         // Field help text
         flagRepeated: 'flag will be repeated N times',
@@ -47,36 +95,84 @@ const translations = {
         language: '语言',
         run: '运行',
         reset: '重置',
-        output: '输出:',
+        share: '分享',
+        linkCopied: '链接已复制到剪贴板',
+        shareLink: '分享此链接: ',
+        invalidSharedState: '无法恢复分享的表单状态: ',
+        output: '输出',
         noOutputYet: '暂无输出。请填写表单并点击"运行"。',
+        clearTerminal: '清空终端',
+        expandAll: '全部展开',
+        collapseAll: '全部折叠',
+        jsonItems: '{n} 项',
+        previousRuns: '之前的运行',
+        pinnedFields: '已置顶',
+        pinField: '置顶',
+        unpinField: '取消置顶',
+        sharedOptions: '所有子命令的选项',
+        usage: '用法',
+        restoreForm: '恢复表单',
+        examples: '示例:',
+        confirmRun: '运行',
+        cancel: '取消',
+        argsBreakdown: '参数明细',
+        argFlag: '标志',
+        argValue: '值',
+        argSource: '字段',
+        noArgs: '无参数',
+        requiredFieldsFilled: '个必填字段已填写',
 
         // Subcommands
         subcommands: '子命令',
         selectSubcommand: '选择子命令',
         selectSubcommandPlaceholder: '-- 选择一个子命令 --',
+        chooseOneSubcommand: '请选择一个：',
+        subcommandRequired: '请选择一个子命令',
         optionsFor: '选项',
 
         // Form fields
         selectOption: '-- 选择一个选项 --',
+        noneOption: '无',
         enterValuePlaceholder: '输入值并按回车',
+        pathPlaceholder: '文件路径',
+        rangeStart: '起始',
+        rangeEnd: '结束',
+        nonUtf8Default: '默认值无法显示（不是有效的 UTF-8）',
+        browse: '浏览…',
+        addGroup: '添加分组',
         requiredField: '必填字段',
+        advancedOptions: '高级选项',
+        extraArgs: '额外参数',
+        extraArgsHelp: '原样追加到命令行；包含空格的值请使用引号',
+        externalSubcommand: '其他（自定义）',
+        externalSubcommandName: '子命令名称',
+        externalSubcommandArgs: '参数',
 
         // Status messages
         loadingWasm: '正在加载 WASM 模块...',
         wasmLoaded: 'WASM 模块加载成功！',
         wasmLoadFailed: 'WASM 模块加载失败: ',
+        wasmImportFailed: '无法加载位于 {path} 的 WASM 模块；是否已运行 wasm-pack build？',
+        wasmModuleMissing: 'window.wasmModule 未设置或没有 {name} 函数',
+        configLoadFailed: '无法从 {path} 加载页面配置',
         wasmNotReady: 'WASM 模块尚未准备就绪，请稍候...',
         running: '正在运行函数...',
         success: '函数执行成功！',
         successNoReturn: '函数执行成功（无返回值）',
         errorOccurred: '发生错误',
-        fixValidationErrors: '请修复验证错误',
-        validationError: '验证错误:',
+        completedIn: '用时 {ms} 毫秒',
+        failedAfter: '{ms} 毫秒后失败',
         error: '错误:',
+        unmappedArgs: '部分参数无法映射到表单: ',
 
         // Validation messages
         fieldRequired: '必填字段为空',
         atLeastOneValue: '至少需要一个值',
+        atLeastNValues: '至少需要 {n} 个值',
+        resetToDefault: '恢复默认值',
+        clearField: '清除',
+        positionalGap: '后续位置参数已填写，此项不能为空',
+        rangeIncomplete: '请填写范围的两端，或都不填',
 
         // Field help text
         flagRepeated: '标志将重复 N 次',
@@ -97,6 +193,11 @@ function getBrowserLanguage() {
 // Current language (initialized from browser or localStorage)
 let currentLanguage = localStorage.getItem('language') || getBrowserLanguage();
 
+// The page, or the shadow root when rendered as a web component
+function uiRoot() {
+    return window.CLI_ROOT || document;
+}
+
 // Get translation for a key
 function t(key) {
     return translations[currentLanguage]?.[key] || translations.en[key] || key;
@@ -114,7 +215,7 @@ function setLanguage(lang) {
 // Update all translatable elements on the page
 function updatePageLanguage() {
     // Update all elements with data-i18n attribute
-    document.querySelectorAll('[data-i18n]').forEach(element => {
+    uiRoot().querySelectorAll('[data-i18n]').forEach(element => {
         const key = element.getAttribute('data-i18n');
         const translation = t(key);
 
@@ -130,8 +231,13 @@ function updatePageLanguage() {
         }
     });
 
+    // Update tooltips of elements with data-i18n-title attribute
+    uiRoot().querySelectorAll('[data-i18n-title]').forEach(element => {
+        element.title = t(element.getAttribute('data-i18n-title'));
+    });
+
     // Update language selector
-    const langSelector = document.getElementById('language-selector');
+    const langSelector = uiRoot().getElementById('language-selector');
     if (langSelector) {
         langSelector.value = currentLanguage;
     }
@@ -140,7 +246,7 @@ function updatePageLanguage() {
 // Initialize i18n when DOM is ready
 function initI18n() {
     // Set up language selector
-    const langSelector = document.getElementById('language-selector');
+    const langSelector = uiRoot().getElementById('language-selector');
     if (langSelector) {
         langSelector.value = currentLanguage;
         langSelector.addEventListener('change', (e) => {
@@ -5,8 +5,12 @@ pub use clap_web_macro::{web_ui_bind, wprintln};
 #[doc(hidden)]
 pub use paste;
 
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
 use serde::Serialize;
-use clap::{Command, Arg, ArgAction};
+use clap::{Command, Arg, ArgAction, ValueHint};
 use maud::{html, Markup, PreEscaped, DOCTYPE};
 
 /// Represents a possible value for an enum field
@@ -16,28 +20,41 @@ pub struct EnumOption {
     pub value: String,
     /// The help text / description for this option (e.g., "This is Option A")
     pub help: String,
+    /// Icon shown before the option's text (e.g. an emoji); clap has no
+    /// such concept, so it comes from the config's `enum_icons`
+    #[serde(default)]
+    pub icon: Option<String>,
 }
 
 /// Type of CLI field for form generation
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 #[serde(tag = "type", content = "options")]
 pub enum FieldType {
     /// String field (text input)
+    #[default]
     String,
     /// Boolean field (checkbox)
     Bool,
     /// Integer field (number input)
     Integer,
+    /// Floating point field (number input accepting decimals)
+    Float,
     /// Counter field (number input, flag repeated N times)
     Counter,
     /// Enum field with possible values
     Enum(Vec<EnumOption>),
     /// Vec field (can add multiple values)
     Vec,
+    /// File system path (`PathBuf`, or an arg with a path `value_hint`); a
+    /// text input with a file picker that fills in the chosen file's name
+    Path,
+    /// Integer range such as `1..10` (fields listed in `int_ranges`): start
+    /// and end number inputs, joined with the separator into one value
+    IntRange,
 }
 
 /// Descriptor for a CLI field
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct FieldDescriptor {
     /// Field name (used as HTML id and for CLI args)
     pub name: String,
@@ -47,29 +64,295 @@ pub struct FieldDescriptor {
     pub long: Option<String>,
     /// Help text / description
     pub help: String,
+    /// clap's `long_help` (e.g. the rest of a multi-paragraph doc comment),
+    /// rendered as a description below the field. Not included in the JSON.
+    #[serde(skip)]
+    pub long_help: Option<String>,
     /// Field type
     pub field_type: FieldType,
-    /// Default value (as string)
+    /// Default value (as string); `Some("")` for an explicit empty default
+    /// (`default_value = ""`), which is distinct from having none
     pub default_value: Option<String>,
     /// Whether the field is required
     pub required: bool,
     /// Whether this is a positional argument (not a flag)
     #[serde(default)]
     pub is_positional: bool,
+    /// Position of a positional argument (1-based, as clap assigns it);
+    /// None for flags
+    #[serde(default)]
+    pub index: Option<usize>,
+    /// Whether enum values are matched case-insensitively (`ignore_case(true)`)
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// Whether the enum's possible values are hidden (`hide_possible_values(true)`);
+    /// such fields render as a text input and carry no options
+    #[serde(default)]
+    pub hide_possible_values: bool,
+    /// Defaults that apply when another arg has a given value (`default_value_if`)
+    #[serde(default)]
+    pub conditional_defaults: Vec<ConditionalDefault>,
+    /// For Bool fields that take a value (e.g. `--color yes` with
+    /// `BoolishValueParser`), the literals the checkbox emits. None for plain
+    /// switches, which emit the bare flag when checked.
+    #[serde(default)]
+    pub bool_values: Option<BoolLiterals>,
+    /// Clap's `value_hint` for the arg (e.g. "Url"), if one was set; text
+    /// inputs use it to pick an input type and placeholder
+    #[serde(default)]
+    pub value_hint: Option<String>,
+    /// Whether the value must be attached with `=` (`--flag=value`), as
+    /// clap requires for `require_equals(true)`
+    #[serde(default)]
+    pub require_equals: bool,
+    /// Whether this positional only takes values after a `--` (clap's
+    /// `last(true)`); its values are emitted last, after the `--`
+    #[serde(default)]
+    pub last: bool,
+    /// The arg's `help_heading`; fields sharing a heading render together
+    /// under it
+    #[serde(default)]
+    pub help_heading: Option<String>,
+    /// Minimum number of values of an open-ended `num_args` range such as
+    /// `1..` (clap gives positional lists `1..` by default); None otherwise
+    #[serde(default)]
+    pub min_values: Option<usize>,
+    /// Token ending a variadic arg's values (clap's `value_terminator`),
+    /// emitted after them so later arguments aren't taken as more values
+    #[serde(default)]
+    pub value_terminator: Option<String>,
+    /// clap's `allow_hyphen_values`: values may start with `-` (e.g. `-5`);
+    /// such values are attached to a long flag as `--flag=value`
+    #[serde(default)]
+    pub allow_hyphen_values: bool,
+    /// Whether the value type looks like a UUID (its type name contains
+    /// `Uuid`, e.g. `uuid::Uuid`). Type names are only available in debug
+    /// builds, so this is always false in release builds.
+    #[serde(default)]
+    pub is_uuid: bool,
+    /// The arg has a default that isn't valid UTF-8 (e.g. from
+    /// `default_value_os`), so it can't be put in the form; `default_value`
+    /// is None and the input's placeholder says so instead
+    #[serde(default)]
+    pub non_utf8_default: bool,
+    /// The flag's place in `--help` (clap's `display_order`, which defaults to
+    /// declaration order); None for positionals
+    #[serde(default)]
+    pub display_order: Option<usize>,
+    /// clap's `hide_short_help`: the arg is left out of `-h` but shown in
+    /// `--help`
+    #[serde(default)]
+    pub hide_short_help: bool,
 }
 
-/// Descriptor for a subcommand
+/// Input type and placeholder for text fields, by value hint
+const VALUE_HINT_INPUTS: &[(&str, &str, &str)] = &[
+    ("Url", "url", "https://example.com"),
+    ("EmailAddress", "email", "user@example.com"),
+    ("Username", "text", "username"),
+    ("Hostname", "text", "example.com"),
+];
+
+/// Pattern for UUID inputs: hyphenated or simple (32 hex digits) form
+const UUID_PATTERN: &str =
+    "[0-9a-fA-F]{8}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{12}";
+
+/// Placeholder for UUID inputs
+const UUID_PLACEHOLDER: &str = "123e4567-e89b-12d3-a456-426614174000";
+
+/// Placeholder of text inputs whose default isn't valid UTF-8
+const NON_UTF8_DEFAULT_PLACEHOLDER: &str = "Default can't be shown (not valid UTF-8)";
+
+/// Title of UUID inputs, which browsers add to their pattern mismatch message
+const UUID_TITLE: &str = "UUID, e.g. 123e4567-e89b-12d3-a456-426614174000";
+
+/// Looks up the input type and placeholder for a field's value hint
+fn value_hint_input(field: &FieldDescriptor) -> Option<(&'static str, &'static str)> {
+    let hint = field.value_hint.as_deref()?;
+    VALUE_HINT_INPUTS.iter()
+        .find(|(name, _, _)| *name == hint)
+        .map(|&(_, input_type, placeholder)| (input_type, placeholder))
+}
+
+/// Values emitted for a checkbox whose flag takes a boolean value
 #[derive(Debug, Clone, Serialize)]
+pub struct BoolLiterals {
+    /// Emitted when checked (default "true")
+    pub true_value: String,
+    /// Emitted when unchecked (default "false")
+    pub false_value: String,
+}
+
+impl Default for BoolLiterals {
+    fn default() -> Self {
+        Self {
+            true_value: "true".to_string(),
+            false_value: "false".to_string(),
+        }
+    }
+}
+
+/// A default that applies only when another arg has a certain value
+/// (clap's `default_value_if`)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConditionalDefault {
+    /// Id of the arg that triggers the default
+    pub trigger: String,
+    /// Value the trigger must have, or None if it only needs to be present
+    pub value: Option<String>,
+    /// The default applied to this field
+    pub default: String,
+}
+
+/// Nesting semantics for a field rendered as groups of values
+///
+/// Used for args that produce nested collections (e.g. `Vec<Vec<String>>` via a
+/// custom parser). Each group becomes one occurrence of the flag.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NestedVecField {
+    /// Delimiter joining a group's values into a single flag value (e.g. "," gives
+    /// `--flag a,b`), or None to pass each value as its own token (`--flag a b`)
+    pub delimiter: Option<String>,
+}
+
+/// Order in which fields are rendered on the page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum FieldOrder {
+    /// Keep clap's declaration order
+    #[default]
+    Declaration,
+    /// Sort flags alphabetically by name; positionals keep their index order
+    Alphabetical,
+    /// Sort flags by clap's `display_order`, matching `--help`; flags with
+    /// equal orders keep their declaration order, positionals their index order
+    DisplayOrder,
+}
+
+/// How a field's help text is presented
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum LabelStyle {
+    /// Use the help text as the label, falling back to the flag/field name
+    #[default]
+    HelpAsLabel,
+    /// Always label with the flag/field name and show the help text as a
+    /// muted description line beneath it
+    NameWithDescription,
+}
+
+/// How the subcommand picker is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum SubcommandStyle {
+    /// A dropdown; the selected subcommand's fields appear below it
+    #[default]
+    Dropdown,
+    /// A row of tabs (`role="tablist"`), one per subcommand, each showing its
+    /// fields as a tab panel; arrow keys move between tabs
+    Tabs,
+}
+
+/// Text direction of the page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum Direction {
+    /// Left to right
+    #[default]
+    Ltr,
+    /// Right to left (Arabic, Hebrew, ...); sets `dir="rtl"` so labels,
+    /// buttons and borders are mirrored
+    Rtl,
+}
+
+impl Direction {
+    /// The `dir` attribute for non-default directions
+    fn dir_attr(self) -> Option<&'static str> {
+        match self {
+            Direction::Ltr => None,
+            Direction::Rtl => Some("rtl"),
+        }
+    }
+}
+
+/// Which form of a flag the page emits when a field has both a short and a long one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum FlagPreference {
+    /// Emit the short form (`-n`) when there is one
+    #[default]
+    ShortWhenAvailable,
+    /// Emit the long form (`--name`); fields without one use the short form
+    AlwaysLong,
+}
+
+/// What happens to the output pane on each run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum OutputMode {
+    /// Each run replaces the previous output
+    #[default]
+    Replace,
+    /// Runs accumulate in a terminal-like scrollback, each under a header with
+    /// its time and arguments, with a "Clear terminal" button
+    Append,
+}
+
+/// How the form is laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum Layout {
+    /// One field per row, with the buttons below the form
+    #[default]
+    Stacked,
+    /// A single line like a search bar: each label beside its input, inputs
+    /// side by side, and the buttons right after them
+    Inline,
+    /// Inline for simple commands, stacked otherwise. A command is simple when
+    /// it has no subcommands or extra-args box and at most
+    /// `INLINE_MAX_FIELDS` (2) fields, all single-value text or number inputs.
+    Auto,
+}
+
+/// Most fields a command can have for `Layout::Auto` to render it inline
+const INLINE_MAX_FIELDS: usize = 2;
+
+/// One checkbox of a checkbox set (see `WasmFunctionConfig::checkbox_sets`)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckboxOption {
+    /// Text shown next to the checkbox
+    pub label: String,
+    /// Value emitted with the flag when checked
+    pub value: String,
+}
+
+/// URLs of the script files a page loads instead of inlining its scripts
+/// (see `WasmFunctionConfig::external_scripts`)
+#[derive(Debug, Clone, Default)]
+pub struct ExternalScripts {
+    /// Where [`I18N_JS`] is written; the same for every page
+    pub i18n: String,
+    /// Where `arg_builder_js()` is written; the same for every page
+    pub arg_builder: String,
+    /// Where the page's own `page_script()` is written
+    pub page: String,
+}
+
+/// Descriptor for a subcommand
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct SubcommandDescriptor {
     /// Subcommand name (e.g., "sub1", "add", "remove")
     pub name: String,
+    /// Visible aliases for this subcommand (e.g., "rm" for "remove")
+    pub aliases: Vec<String>,
     /// Help text / description for this subcommand
     pub help: String,
     /// Fields specific to this subcommand
     pub fields: Vec<FieldDescriptor>,
+    /// clap's usage line for the subcommand, including the parent command
+    /// (e.g. "example add [OPTIONS] <FILE>"); shown with the page's `usage`
+    /// while the subcommand is selected
+    pub usage: Option<String>,
 }
 
 /// Configuration for generating a WASM function web interface
+///
+/// The whole config serializes to JSON (see `config_to_json`); the same JSON
+/// is embedded in the page as `window.CLI_CONFIG`.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct WasmFunctionConfig {
     /// The name of the WASM function to call (e.g., "process")
     pub function_name: String,
@@ -83,6 +366,498 @@ pub struct WasmFunctionConfig {
     pub fields: Vec<FieldDescriptor>,
     /// Subcommand descriptors (if any)
     pub subcommands: Vec<SubcommandDescriptor>,
+    /// Example invocations rendered as clickable chips that fill the form
+    /// (e.g., "example --name foo sub1 bar", including the program name)
+    pub examples: Vec<String>,
+    /// Show only required fields without a default up front and collapse the
+    /// rest into an "Advanced options" expander (they still submit their defaults)
+    pub collapse_optional_fields: bool,
+    /// Fields (by name) always rendered in the collapsed "Advanced options"
+    /// expander, even when required; it's opened when one of them is invalid
+    pub advanced_fields: BTreeSet<String>,
+    /// Fields (by name) rendered as groups of values instead of a flat list
+    pub nested_fields: BTreeMap<String, NestedVecField>,
+    /// Show an "Extra arguments" textarea whose content is tokenized (respecting
+    /// quotes) and appended to the assembled args, for args the form doesn't model
+    pub extra_args: bool,
+    /// Name of the field to emphasize as the main input: it renders first in its
+    /// section, larger, and with autofocus
+    pub primary_field: Option<String>,
+    /// Step sizes (by field name) for number inputs, emitted as the `step`
+    /// attribute (e.g., "100" or "0.5"). Unlisted fields step by 1 (integers)
+    /// or any amount (floats).
+    pub field_steps: BTreeMap<String, String>,
+    /// Order of fields within each section (main command and subcommands)
+    pub field_order: FieldOrder,
+    /// How field labels and help text are rendered
+    pub label_style: LabelStyle,
+    /// Render `` `code` `` spans in labels and help text as `<code>` elements
+    pub inline_code: bool,
+    /// Render UUID-typed fields (see `FieldDescriptor::is_uuid`) as inputs
+    /// with a UUID pattern and placeholder, so invalid values are flagged
+    /// before running
+    pub uuid_inputs: bool,
+    /// Whether the form is stacked or a single inline row
+    pub layout: Layout,
+    /// Run the function once on page load, but only if the form passes
+    /// validation as populated (e.g. every required field has a default)
+    pub auto_run: bool,
+    /// Categories (by subcommand name) for grouping the subcommand selector
+    /// into `<optgroup>`s; uncategorized subcommands are listed first
+    pub subcommand_categories: BTreeMap<String, String>,
+    /// Custom HTML shell replacing the built-in outer page (for meta tags,
+    /// analytics, fonts, ...). The generated pieces are substituted for the
+    /// `{{TITLE}}`, `{{META}}`, `{{STYLES}}`, `{{FORM}}` and `{{SCRIPT}}`
+    /// placeholders; `{{SCRIPT}}` must come after `{{FORM}}`. Not included in
+    /// the JSON.
+    #[serde(skip)]
+    pub html_shell: Option<String>,
+    /// Text for the page's `<meta name="description">` and Open Graph tags
+    /// (`og:title` from the page title, `og:description`), used by search
+    /// engines and link previews. `build_config_for_parser` fills it in from
+    /// the command's about text; the tags are omitted when it's empty. Not
+    /// included in the JSON.
+    #[serde(skip)]
+    pub meta_description: Option<String>,
+    /// Extra markup for the page's `<head>` (favicons, fonts, analytics,
+    /// meta tags, ...), inserted after the title and styles. It's trusted
+    /// raw HTML and isn't escaped. With `html_shell` it follows the
+    /// `{{STYLES}}`; web components have no head, so it's ignored there. Not
+    /// included in the JSON.
+    #[serde(skip)]
+    pub head_extra: Option<String>,
+    /// Text direction; `Direction::Rtl` sets `dir="rtl"` on `<html>` and the
+    /// page container. With `bare` there's no container, so the host page
+    /// sets `dir` itself
+    pub direction: Direction,
+    /// Emit a `<clap-web-ui>` custom element (plus its definition and scripts)
+    /// instead of a full page, for embedding into an existing page. The markup
+    /// and styles live in the element's shadow root, so they don't collide with
+    /// the host page's CSS. Only one element per page is supported, and
+    /// `html_shell` is ignored.
+    pub web_component: bool,
+    /// Offer an "Other (custom)" subcommand with free-form name and arguments,
+    /// for commands with `allow_external_subcommands(true)`
+    pub allow_external_subcommands: bool,
+    /// Render flags that every subcommand has, identically (e.g. flattened
+    /// common args), once in a shared section instead of in each subcommand's
+    /// section. They're still passed after the chosen subcommand's name
+    pub shared_subcommand_fields: bool,
+    /// Whether subcommands are picked from a dropdown or a row of tabs
+    pub subcommand_style: SubcommandStyle,
+    /// One of the subcommands must be chosen (clap's `subcommand_required`):
+    /// the picker gets a "Choose one:" hint and is marked required, and Run
+    /// fails validation until a subcommand is selected
+    pub subcommand_required: bool,
+    /// clap's usage line (e.g. "example [OPTIONS] --name <NAME>", one line per
+    /// usage), shown in a monospace block above the form; configs built from a
+    /// `Command` have it. Subcommands' usages replace it while selected
+    pub usage: Option<String>,
+    /// Show a breadcrumb of the current command path (e.g. "example > add")
+    /// above the form, following the subcommand selection
+    pub breadcrumb: bool,
+    /// Program name at the root of the breadcrumb (the command's bin name
+    /// for configs built from a `Command`); the page title when None. Not
+    /// included in the JSON.
+    #[serde(skip)]
+    pub command_name: Option<String>,
+    /// Units (by field name) shown as a suffix inside number inputs (e.g. "s",
+    /// "MB"); purely cosmetic, the submitted value excludes the unit
+    pub field_units: BTreeMap<String, String>,
+    /// Fields (by name) rendered as a fixed set of checkboxes, e.g. feature
+    /// toggles; each checked box emits `--flag <value>`
+    pub checkbox_sets: BTreeMap<String, Vec<CheckboxOption>>,
+    /// Fields (by name) taking an integer range through a custom value parser,
+    /// mapped to the separator it expects (e.g. ".." for `1..10`). They render
+    /// as `FieldType::IntRange`: start and end inputs joined into one value
+    pub int_ranges: BTreeMap<String, String>,
+    /// Groups of bool fields (by name) rendered together as a segmented
+    /// button bar, one toggle per field, instead of separate checkboxes
+    pub segmented_groups: Vec<Vec<String>>,
+    /// String fields (by name) rendered as a multi-line textarea, e.g. a
+    /// `--script` flag; the whole text is passed as the flag's single value
+    pub textarea_fields: BTreeSet<String>,
+    /// Render enums with at most this many options (e.g. 4) as a row of
+    /// radio-style buttons instead of a dropdown; None keeps every enum a
+    /// dropdown
+    pub enum_buttons: Option<usize>,
+    /// Icons (by option value, e.g. "fast" -> "⚡") shown before enum options'
+    /// text, in dropdowns and enum buttons alike. Dropdown options can only
+    /// hold text, so icons are text such as emoji
+    pub enum_icons: BTreeMap<String, String>,
+    /// Counter fields (by name) rendered as a row of level buttons instead of
+    /// a number input, e.g. `-v` with `["Off", "Info", "Debug", "Trace"]`:
+    /// the button at index N repeats the flag N times
+    pub counter_levels: BTreeMap<String, Vec<String>>,
+    /// Leave args with clap's `hide_short_help` out of the form (and the
+    /// JSON), treating the form like `-h` rather than `--help`. Required args
+    /// without a default are kept, since runs would fail without them
+    pub hide_short_help_fields: bool,
+    /// Web-specific defaults (by field name, in the main command or any
+    /// subcommand) that replace the extracted ones, e.g. a safer mode for web
+    /// users; the CLI keeps its own defaults. They seed inputs and preselect
+    /// enum options. Applied when the page and JSON are generated, so they
+    /// show up as the fields' `default_value` there. Not included in the JSON.
+    #[serde(skip)]
+    pub default_overrides: BTreeMap<String, String>,
+    /// Use the already loaded and initialized WASM module the host page puts
+    /// in `window.wasmModule` (or a promise of it), e.g. in a single-page app,
+    /// instead of importing and instantiating the package's module
+    pub external_wasm: bool,
+    /// URL (relative to the page) of a sidecar JSON file the page fetches its
+    /// configuration from, instead of the inline `window.CLI_CONFIG` script;
+    /// for Content-Security-Policies that forbid inline scripts. Write the
+    /// `config_to_json` output to that location. Not included in the JSON.
+    #[serde(skip)]
+    pub external_config: Option<String>,
+    /// URL (relative to the page) of an ES module exporting the config as
+    /// `CLI_CONFIG`, which the main script imports instead of reading the
+    /// inline `window.CLI_CONFIG` global (and in place of `external_config`).
+    /// Write the `config_to_js_module` output to that location. Not included
+    /// in the JSON.
+    #[serde(skip)]
+    pub config_module: Option<String>,
+    /// URL (relative to the page) of the stylesheet to link instead of
+    /// inlining `cli-ui.css`, so several pages can share one cached file.
+    /// Write [`CLI_UI_CSS`] to that location. Not included in the JSON.
+    #[serde(skip)]
+    pub external_css: Option<String>,
+    /// URLs (relative to the page) of script files loaded instead of the
+    /// inline scripts. Together with `external_config` or `config_module` the
+    /// page then has no inline script at all, for a Content-Security-Policy
+    /// such as `script-src 'self'` (only `web_component`'s element definition
+    /// stays inline). Not included in the JSON.
+    #[serde(skip)]
+    pub external_scripts: Option<ExternalScripts>,
+    /// Generate a bare page for a host that provides all the styling: no
+    /// stylesheet (inline or linked) and no `.container` wrapper with the
+    /// title and language selector, just the form, output and scripts
+    #[serde(skip)]
+    pub bare: bool,
+    /// Nonce emitted on the generated `<script>` and `<style>` tags, for a
+    /// Content-Security-Policy such as `script-src 'nonce-...'`. It has to
+    /// match the nonce the server sends in the policy. Not included in the JSON.
+    #[serde(skip)]
+    pub script_nonce: Option<String>,
+    /// Render a small "×" button on text, number, select and list fields that
+    /// resets just that field to its default (or empty)
+    pub clear_buttons: bool,
+    /// Show a small "↺" button beside fields that have a default while their
+    /// value differs from it; clicking it restores the default
+    pub reset_buttons: bool,
+    /// Show a Share button that copies a link with the form state encoded in
+    /// its `#state=` fragment; pages opened from such a link restore the form
+    pub share_button: bool,
+    /// Which form of a flag the emitted arguments use; counters repeat the
+    /// chosen form
+    pub flag_preference: FlagPreference,
+    /// Whether runs replace the output or accumulate in a scrollback
+    pub output_mode: OutputMode,
+    /// Number the output lines in a gutter beside the text. The numbers
+    /// aren't part of the text, so copying the output gives the raw text
+    pub output_line_numbers: bool,
+    /// Show output that is a JSON object or array as a collapsible tree, with
+    /// "Expand all" and "Collapse all" buttons; other output stays text
+    pub json_tree: bool,
+    /// Keep the previous runs (arguments, output and form state) in a
+    /// "Previous runs" dropdown above the output. Choosing one shows its
+    /// output, and its form can then be restored
+    pub run_history: bool,
+    /// How many runs `run_history` keeps (5 when None)
+    pub run_history_size: Option<usize>,
+    /// Keep the run history in localStorage so it survives reloads; it's
+    /// only kept in memory otherwise
+    pub persist_run_history: bool,
+    /// Give each main command field a pin button that moves it to a "Pinned"
+    /// section at the top of the form, for forms used over and over. Pins
+    /// are kept in localStorage; pinned fields are submitted as usual
+    pub pinned_fields: bool,
+    /// Message of an "Are you sure?" dialog shown before each run, for tools
+    /// with destructive actions; runs only go ahead once confirmed
+    pub confirm_run: Option<String>,
+    /// Show how long each run's WASM call took in the status line (e.g.
+    /// "Completed in 123 ms"), measured in the browser; the status stays
+    /// until the next run instead of fading
+    pub show_timing: bool,
+    /// Show an "Args breakdown" table below the form listing each active arg
+    /// (flag, value and the field it comes from), updated as the form changes
+    pub args_breakdown: bool,
+    /// Show how many required fields (of the main command and the selected
+    /// subcommand) are filled, e.g. "2/3 required fields filled", next to
+    /// Run, updated as the form changes; Run stays disabled until all are
+    pub required_summary: bool,
+    /// Emit each field's type, default, required flag and short/long flags as
+    /// `data-field-type`, `data-default`, `data-required`, `data-short` and
+    /// `data-long` attributes on its `.field-group`, for scripts that work
+    /// from the DOM instead of `window.CLI_CONFIG`
+    pub field_data_attributes: bool,
+}
+
+impl WasmFunctionConfig {
+    /// The config with `default_overrides`, `int_ranges`, `enum_icons` and
+    /// `hide_short_help_fields` applied to its fields (borrowed as-is when
+    /// there are none)
+    fn with_field_overrides(&self) -> Cow<'_, WasmFunctionConfig> {
+        if self.default_overrides.is_empty()
+            && self.int_ranges.is_empty()
+            && self.enum_icons.is_empty()
+            && !self.hide_short_help_fields
+        {
+            return Cow::Borrowed(self);
+        }
+
+        let mut config = self.clone();
+        if self.hide_short_help_fields {
+            let shown = |f: &FieldDescriptor| !f.hide_short_help || (f.required && f.default_value.is_none());
+            config.fields.retain(shown);
+            for subcmd in &mut config.subcommands {
+                subcmd.fields.retain(shown);
+            }
+        }
+        let fields = config.fields.iter_mut()
+            .chain(config.subcommands.iter_mut().flat_map(|sc| sc.fields.iter_mut()));
+        for field in fields {
+            if let Some(default) = self.default_overrides.get(&field.name) {
+                field.default_value = Some(default.clone());
+            }
+            if self.int_ranges.contains_key(&field.name) {
+                field.field_type = FieldType::IntRange;
+            }
+            if let FieldType::Enum(options) = &mut field.field_type {
+                for option in options.iter_mut().filter(|o| o.icon.is_none()) {
+                    option.icon = self.enum_icons.get(&option.value).cloned();
+                }
+            }
+        }
+        Cow::Owned(config)
+    }
+
+    /// Whether the form renders as a single inline row (see `Layout`)
+    fn is_inline(&self) -> bool {
+        match self.layout {
+            Layout::Stacked => false,
+            Layout::Inline => true,
+            Layout::Auto => {
+                self.subcommands.is_empty()
+                    && !self.extra_args
+                    && !self.allow_external_subcommands
+                    && (1..=INLINE_MAX_FIELDS).contains(&self.fields.len())
+                    && self.fields.iter().all(|field| {
+                        matches!(field.field_type, FieldType::String | FieldType::Integer | FieldType::Float)
+                    })
+            }
+        }
+    }
+
+    /// Ids of the page's own elements, which field ids must not reuse: the ones
+    /// every page has, plus those of the parts this config renders
+    fn reserved_ids(&self) -> Vec<String> {
+        let has_subcommands = !self.subcommands.is_empty() || self.allow_external_subcommands;
+        let parts: [(bool, &[&str]); 14] = [
+            (true, &["cliForm", "runButton", "clearButton", "output", "output-subcommand", "status"]),
+            (!self.bare, &["language-selector"]),
+            (self.web_component, &["clap-web-ui-template"]),
+            (self.usage.is_some(), &["usage"]),
+            (self.breadcrumb, &["breadcrumb"]),
+            (self.pinned_fields, &["pinnedFields"]),
+            (self.extra_args, &["extra-args"]),
+            (self.share_button, &["shareButton"]),
+            (self.required_summary, &["requiredSummary"]),
+            (self.args_breakdown, &["argsBreakdown"]),
+            (self.run_history, &["runHistory", "restoreRunButton"]),
+            (self.json_tree, &["expandAllButton", "collapseAllButton"]),
+            (self.output_mode == OutputMode::Append, &["clearOutputButton"]),
+            (has_subcommands, &["subcommand-selector"]),
+        ];
+        let mut ids: Vec<String> = parts.into_iter()
+            .filter(|(rendered, _)| *rendered)
+            .flat_map(|(_, ids)| ids.iter().map(|id| id.to_string()))
+            .collect();
+        if self.allow_external_subcommands {
+            ids.extend(["external-subcommand-name", "external-subcommand-args"].map(String::from));
+            ids.push(format!("subcommand-{}", EXTERNAL_SUBCOMMAND));
+            if self.subcommand_style == SubcommandStyle::Tabs {
+                ids.push(format!("subcommand-tab-{}", EXTERNAL_SUBCOMMAND));
+            }
+        }
+        ids
+    }
+
+    /// Checks the config for problems that would produce a broken page
+    ///
+    /// It checks for empty function, package, subcommand and field names,
+    /// duplicate field and subcommand names, field ids that collide with each
+    /// other or with the page's own elements, and enum fields without options.
+    /// All problems are returned, not just the first.
+    ///
+    /// The generator binary prints these problems, and the `generate_<fn>_ui`
+    /// functions of `#[web_ui_bind]` return them; `generate_wasm_function_page`
+    /// doesn't check, so call this yourself before rendering a config directly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clap_web_code_gen::{ConfigError, WasmFunctionConfig};
+    ///
+    /// let config = WasmFunctionConfig {
+    ///     package_name: "example".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.validate(), Err(vec![ConfigError::EmptyFunctionName]));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        if self.function_name.is_empty() {
+            errors.push(ConfigError::EmptyFunctionName);
+        }
+        if self.package_name.is_empty() {
+            errors.push(ConfigError::EmptyPackageName);
+        }
+
+        let mut ids: BTreeSet<String> = self.reserved_ids().into_iter().collect();
+        let mut subcommand_names = BTreeSet::new();
+        for subcmd in &self.subcommands {
+            if subcmd.name.is_empty() {
+                errors.push(ConfigError::EmptySubcommandName);
+            } else if !subcommand_names.insert(subcmd.name.as_str()) {
+                errors.push(ConfigError::DuplicateSubcommand { name: subcmd.name.clone() });
+            } else {
+                ids.insert(format!("subcommand-{}", subcmd.name));
+                if self.subcommand_style == SubcommandStyle::Tabs {
+                    ids.insert(format!("subcommand-tab-{}", subcmd.name));
+                }
+            }
+        }
+
+        let sections = std::iter::once((None, &self.fields))
+            .chain(self.subcommands.iter().map(|s| (Some(s.name.as_str()), &s.fields)));
+        for (subcommand, fields) in sections {
+            let mut names = BTreeSet::new();
+            for field in fields {
+                let subcommand = subcommand.map(str::to_string);
+                if field.name.is_empty() {
+                    errors.push(ConfigError::EmptyFieldName { subcommand });
+                    continue;
+                }
+                if !names.insert(field.name.as_str()) {
+                    errors.push(ConfigError::DuplicateFieldName { subcommand, name: field.name.clone() });
+                    continue;
+                }
+                if matches!(&field.field_type, FieldType::Enum(options) if options.is_empty())
+                    && !field.hide_possible_values
+                {
+                    errors.push(ConfigError::EmptyEnum { subcommand: subcommand.clone(), name: field.name.clone() });
+                }
+
+                // Same scheme as generate_form_field
+                let id = match &subcommand {
+                    Some(prefix) => format!("{}-{}", prefix, field.name),
+                    None => field.name.clone(),
+                };
+                if !ids.insert(id.clone()) {
+                    errors.push(ConfigError::ConflictingId { id });
+                }
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Lists the parts of the command the page can't fully represent
+    ///
+    /// Unlike `validate` problems, these don't break the page; the generator
+    /// prints them so they aren't missed.
+    pub fn warnings(&self) -> Vec<ConfigWarning> {
+        let sections = std::iter::once((None, &self.fields))
+            .chain(self.subcommands.iter().map(|s| (Some(&s.name), &s.fields)));
+        sections
+            .flat_map(|(subcommand, fields)| {
+                fields.iter()
+                    .filter(|field| field.non_utf8_default)
+                    .map(move |field| ConfigWarning::NonUtf8Default {
+                        subcommand: subcommand.cloned(),
+                        name: field.name.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// A problem found by `WasmFunctionConfig::validate`
+///
+/// `subcommand` is None for fields of the main command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `function_name` is empty
+    EmptyFunctionName,
+    /// `package_name` is empty
+    EmptyPackageName,
+    /// A subcommand has an empty name
+    EmptySubcommandName,
+    /// Two subcommands share a name
+    DuplicateSubcommand { name: String },
+    /// A field has an empty name
+    EmptyFieldName { subcommand: Option<String> },
+    /// Two fields of the same command share a name
+    DuplicateFieldName { subcommand: Option<String>, name: String },
+    /// A field's HTML id is already used by another field or by the page itself
+    /// (e.g. a main command field "add-name" and field "name" of subcommand "add")
+    ConflictingId { id: String },
+    /// An enum field has no options (and doesn't hide them on purpose)
+    EmptyEnum { subcommand: Option<String>, name: String },
+}
+
+/// Describes a field for messages, e.g. "field 'name' of subcommand 'add'"
+fn describe_field(subcommand: &Option<String>, name: &str) -> String {
+    match subcommand {
+        Some(subcommand) => format!("field '{}' of subcommand '{}'", name, subcommand),
+        None => format!("field '{}'", name),
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EmptyFunctionName => write!(f, "function name is empty"),
+            ConfigError::EmptyPackageName => write!(f, "package name is empty"),
+            ConfigError::EmptySubcommandName => write!(f, "a subcommand has an empty name"),
+            ConfigError::DuplicateSubcommand { name } => write!(f, "subcommand '{}' is defined more than once", name),
+            ConfigError::EmptyFieldName { subcommand: Some(subcommand) } => {
+                write!(f, "a field of subcommand '{}' has an empty name", subcommand)
+            }
+            ConfigError::EmptyFieldName { subcommand: None } => write!(f, "a field has an empty name"),
+            ConfigError::DuplicateFieldName { subcommand, name } => {
+                write!(f, "{} is defined more than once", describe_field(subcommand, name))
+            }
+            ConfigError::ConflictingId { id } => write!(f, "HTML id '{}' is used more than once", id),
+            ConfigError::EmptyEnum { subcommand, name } => write!(f, "{} has no options", describe_field(subcommand, name)),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Something `WasmFunctionConfig::warnings` found the page can't represent
+///
+/// `subcommand` is None for fields of the main command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    /// A field's default isn't valid UTF-8 (e.g. from `default_value_os`), so
+    /// the form leaves it out
+    NonUtf8Default { subcommand: Option<String>, name: String },
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigWarning::NonUtf8Default { subcommand, name } => write!(
+                f,
+                "{} has a default that isn't valid UTF-8; the form leaves it out",
+                describe_field(subcommand, name)
+            ),
+        }
+    }
 }
 
 /// Extracts field descriptors from a Clap Command
@@ -103,7 +878,7 @@ pub struct WasmFunctionConfig {
 ///
 /// ```
 /// use clap::{Parser, CommandFactory};
-/// use code_gen::extract_field_descriptors_from_command;
+/// use clap_web_code_gen::extract_field_descriptors_from_command;
 ///
 /// #[derive(Parser)]
 /// struct MyArgs {
@@ -118,27 +893,61 @@ pub fn extract_field_descriptors_from_command(command: &Command) -> Vec<FieldDes
     extract_fields_from_arguments(command.get_arguments())
 }
 
+/// Returns the program name to pass as argv[0] when parsing args for a command
+///
+/// This is the command's `bin_name` if one was set explicitly, otherwise its
+/// `name`, so usage and error messages match the real binary.
+///
+/// # Example
+///
+/// ```
+/// use clap::Command;
+/// use clap_web_code_gen::program_name;
+///
+/// assert_eq!(program_name(&Command::new("tool")), "tool");
+/// assert_eq!(program_name(&Command::new("tool").bin_name("cargo tool")), "cargo tool");
+/// ```
+pub fn program_name(command: &Command) -> String {
+    command.get_bin_name().unwrap_or(command.get_name()).to_string()
+}
+
 /// Helper function to extract field descriptors from command arguments
 fn extract_fields_from_arguments<'a>(
     args: impl Iterator<Item = &'a Arg> + 'a
 ) -> Vec<FieldDescriptor> {
+    // Positionals without an explicit index are numbered in declaration
+    // order, the same way clap does when it builds the command
+    let mut next_index = 1;
+
     args
         .filter(|arg| {
-            // Skip help and version arguments
-            let id = arg.get_id().as_str();
-            id != "help" && id != "version"
+            // Skip help and version arguments, whatever their id (e.g. a
+            // custom `-?` help flag)
+            !matches!(
+                arg.get_action(),
+                ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+            )
         })
         .map(|arg| {
             let name = arg.get_id().as_str().to_string();
-            let short = arg.get_short().map(|c| c);
+            let short = arg.get_short();
             let long = arg.get_long().map(|s| s.to_string());
             let help = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+            let long_help = arg.get_long_help().map(|h| h.to_string());
             let is_positional = arg.is_positional();
+            let index = is_positional.then(|| {
+                arg.get_index().unwrap_or_else(|| {
+                    next_index += 1;
+                    next_index - 1
+                })
+            });
 
             // Get default value
-            let default_value = arg.get_default_values()
-                .first()
-                .and_then(|d| d.to_str().map(|s| s.to_string()));
+            // A non-UTF-8 default (default_value_os) can't go in the form, but
+            // is flagged rather than dropped silently
+            let default_os = arg.get_default_values().first();
+            let default_value = default_os.and_then(|d| d.to_str().map(|s| s.to_string()));
+            let non_utf8_default = default_os.is_some_and(|d| d.to_str().is_none());
 
             // Determine field type based on action and value parser
             let field_type = determine_field_type_from_arg(arg);
@@ -146,6 +955,18 @@ fn extract_fields_from_arguments<'a>(
             // Determine if required
             let required = arg.is_required_set();
 
+            // Bools that aren't switches take an explicit value (`--flag true`)
+            let bool_values = (matches!(field_type, FieldType::Bool) && !is_bool_arg(arg))
+                .then(BoolLiterals::default);
+
+            let value_hint = match arg.get_value_hint() {
+                ValueHint::Unknown => None,
+                hint => Some(format!("{:?}", hint)),
+            };
+
+            let is_uuid = matches!(field_type, FieldType::String)
+                && format!("{:?}", arg.get_value_parser().type_id()).contains("Uuid");
+
             FieldDescriptor {
                 name,
                 short,
@@ -155,11 +976,121 @@ fn extract_fields_from_arguments<'a>(
                 default_value,
                 required,
                 is_positional,
+                index,
+                ignore_case: arg.is_ignore_case_set(),
+                hide_possible_values: arg.is_hide_possible_values_set(),
+                conditional_defaults: extract_conditional_defaults(arg),
+                bool_values,
+                value_hint,
+                require_equals: arg.is_require_equals_set(),
+                last: arg.is_last_set(),
+                help_heading: arg.get_help_heading().map(|h| h.to_string()),
+                min_values: arg.get_num_args()
+                    .filter(|n| n.max_values() == usize::MAX && n.min_values() > 0)
+                    .map(|n| n.min_values()),
+                value_terminator: arg.get_value_terminator().map(|t| t.to_string()),
+                allow_hyphen_values: arg.is_allow_hyphen_values_set(),
+                is_uuid,
+                non_utf8_default,
+                display_order: (!is_positional).then(|| arg.get_display_order()),
+                hide_short_help: arg.is_hide_short_help_set(),
+                long_help,
             }
         })
         .collect()
 }
 
+/// A command's usage as clap renders it, without the "Usage:" heading and
+/// with one usage per line
+fn render_usage_text(command: &mut Command) -> String {
+    let usage = command.render_usage().to_string();
+    usage.strip_prefix("Usage:").unwrap_or(&usage)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Extracts `default_value_if` rules from an arg
+///
+/// Clap has no public getter for these, so (like the type detection in
+/// `determine_field_type_from_arg`) they are read from the arg's `Debug`
+/// output, e.g. `default_vals_ifs: [("mode", Equals("fast"), Some(["1"]))]`.
+/// Rules that unset the default or set several values are skipped.
+fn extract_conditional_defaults(arg: &Arg) -> Vec<ConditionalDefault> {
+    parse_conditional_defaults(&format!("{:?}", arg)).unwrap_or_default()
+}
+
+fn parse_conditional_defaults(debug: &str) -> Option<Vec<ConditionalDefault>> {
+    const KEY: &str = "default_vals_ifs: [";
+    let mut rest = &debug[debug.find(KEY)? + KEY.len()..];
+    let mut rules = Vec::new();
+
+    // Each rule is `("trigger", IsPresent | Equals("value"), None | Some(["default", ...]))`
+    while let Some(r) = rest.strip_prefix('(') {
+        let (trigger, r) = parse_debug_str(r)?;
+        let r = r.strip_prefix(", ")?;
+        let (value, r) = match r.strip_prefix("IsPresent") {
+            Some(r) => (None, r),
+            None => {
+                let (value, r) = parse_debug_str(r.strip_prefix("Equals(")?)?;
+                (Some(value), r.strip_prefix(')')?)
+            }
+        };
+        let r = r.strip_prefix(", ")?;
+        let (defaults, r) = match r.strip_prefix("None") {
+            Some(r) => (Vec::new(), r),
+            None => {
+                let mut r = r.strip_prefix("Some([")?;
+                let mut defaults = Vec::new();
+                while let Some((default, next)) = parse_debug_str(r) {
+                    defaults.push(default);
+                    r = next.strip_prefix(", ").unwrap_or(next);
+                }
+                (defaults, r.strip_prefix("])")?)
+            }
+        };
+        let r = r.strip_prefix(')')?;
+        rest = r.strip_prefix(", ").unwrap_or(r);
+
+        if let [default] = defaults.as_slice() {
+            rules.push(ConditionalDefault { trigger, value, default: default.clone() });
+        }
+    }
+
+    Some(rules)
+}
+
+/// Parses a leading `Debug`-formatted string literal, returning it unescaped
+/// along with the remaining input
+fn parse_debug_str(s: &str) -> Option<(String, &str)> {
+    let mut chars = s.strip_prefix('"')?.char_indices();
+    let mut out = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            // `i` is relative to the text after the opening quote
+            '"' => return Some((out, &s[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                '0' => out.push('\0'),
+                'u' => {
+                    // \u{XXXX}
+                    let hex: String = chars.by_ref().skip(1).map(|(_, c)| c).take_while(|&c| c != '}').collect();
+                    out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                escaped => out.push(escaped),
+            },
+            c => out.push(c),
+        }
+    }
+
+    None
+}
+
 /// Extracts subcommand descriptors from a Clap Command
 ///
 /// This function introspects a Clap Command to extract all subcommands
@@ -173,6 +1104,10 @@ fn extract_fields_from_arguments<'a>(
 ///
 /// A Vec of SubcommandDescriptor objects representing all subcommands
 pub fn extract_subcommands_from_command(command: &Command) -> Vec<SubcommandDescriptor> {
+    // Built so each subcommand's usage starts with the full command path
+    let mut built = command.clone();
+    built.build();
+
     command
         .get_subcommands()
         .filter(|subcmd| {
@@ -181,31 +1116,95 @@ pub fn extract_subcommands_from_command(command: &Command) -> Vec<SubcommandDesc
         })
         .map(|subcmd| {
             let name = subcmd.get_name().to_string();
+            let aliases = subcmd.get_visible_aliases().map(|a| a.to_string()).collect();
             let help = subcmd.get_about()
                 .map(|a| a.to_string())
                 .unwrap_or_default();
-            let fields = extract_fields_from_arguments(subcmd.get_arguments());
+            // Once the command is built, clap copies global args into every
+            // subcommand; they're already in the top-level section
+            let fields = extract_fields_from_arguments(subcmd.get_arguments().filter(|arg| {
+                !(arg.is_global_set() && command.get_arguments().any(|a| a.get_id() == arg.get_id()))
+            }));
+
+            let usage = built.find_subcommand_mut(&name).map(render_usage_text);
 
             SubcommandDescriptor {
                 name,
+                aliases,
                 help,
                 fields,
+                usage,
             }
         })
         .collect()
 }
 
+/// Extracts example invocations from a Clap Command's after-help text
+///
+/// Clap doesn't model examples, so this follows a simple convention: a line
+/// reading `Examples:` in `after_help` (or `after_long_help`) starts an
+/// examples block, and every following line starting with `$ ` is taken as
+/// one invocation (including the program name). The block ends at the first
+/// empty line.
+///
+/// ```text
+/// Examples:
+///   $ example --name foo
+///   $ example sub1 "some text"
+/// ```
+///
+/// # Arguments
+///
+/// * `command` - A Clap Command object
+///
+/// # Returns
+///
+/// A Vec of example command lines with the `$ ` prompt stripped
+pub fn extract_examples_from_command(command: &Command) -> Vec<String> {
+    let mut examples = Vec::new();
+
+    for text in [command.get_after_help(), command.get_after_long_help()].into_iter().flatten() {
+        let text = text.to_string();
+        let mut in_examples = false;
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if !in_examples {
+                in_examples = trimmed.eq_ignore_ascii_case("examples:");
+                continue;
+            }
+            if trimmed.is_empty() {
+                break;
+            }
+            if let Some(example) = trimmed.strip_prefix("$ ") {
+                let example = example.trim().to_string();
+                if !examples.contains(&example) {
+                    examples.push(example);
+                }
+            }
+        }
+    }
+
+    examples
+}
+
 fn determine_field_type_from_arg(arg: &Arg) -> FieldType {
     let action = arg.get_action();
 
+    // Everything after `--` goes to a `last(true)` positional, so it takes a
+    // list of values whatever its type
+    if arg.is_last_set() {
+        return FieldType::Vec;
+    }
+
     // Check action type first
     match action {
-        ArgAction::SetTrue | ArgAction::SetFalse | ArgAction::Set if is_bool_arg(arg) => {
-            return FieldType::Bool;
-        }
         ArgAction::Count => {
             return FieldType::Counter;
         }
+        _ if is_bool_arg(arg) => {
+            return FieldType::Bool;
+        }
         ArgAction::Append => {
             return FieldType::Vec;
         }
@@ -218,25 +1217,42 @@ fn determine_field_type_from_arg(arg: &Arg) -> FieldType {
         return FieldType::Vec;
     }
 
+    // Try to infer from value parser type name
+    let type_id = arg.get_value_parser().type_id();
+    let type_name = format!("{:?}", type_id);
+
+    // Bool parsers (including boolish ones) list their literals as possible
+    // values, so check for them before enums
+    if type_name == "bool" {
+        return FieldType::Bool;
+    }
+
     // Check if it's an enum (has possible values)
     if let Some(value_parser) = arg.get_value_parser().possible_values() {
         let options: Vec<EnumOption> = value_parser
             .map(|pv| EnumOption {
                 value: pv.get_name().to_string(),
                 help: pv.get_help().map(|h| h.to_string()).unwrap_or_default(),
+                icon: None,
             })
             .collect();
         if !options.is_empty() {
+            // Hidden values aren't exposed anywhere in the page, including the
+            // embedded JSON config
+            if arg.is_hide_possible_values_set() {
+                return FieldType::Enum(Vec::new());
+            }
             return FieldType::Enum(options);
         }
     }
 
-    // Try to infer from value parser type name
-    let type_id = arg.get_value_parser().type_id();
-    let type_name = format!("{:?}", type_id);
-
-    if type_name.contains("bool") {
-        return FieldType::Bool;
+    // Clap reports PathBuf args as `AnyPath` unless another hint is set, so
+    // this also works in release builds (unlike the type name checks below)
+    if matches!(
+        arg.get_value_hint(),
+        ValueHint::AnyPath | ValueHint::FilePath | ValueHint::DirPath | ValueHint::ExecutablePath
+    ) {
+        return FieldType::Path;
     }
 
     if type_name.contains("u8") || type_name.contains("u16") || type_name.contains("u32")
@@ -246,14 +1262,30 @@ fn determine_field_type_from_arg(arg: &Arg) -> FieldType {
         return FieldType::Integer;
     }
 
+    if type_name.contains("f32") || type_name.contains("f64") {
+        return FieldType::Float;
+    }
+
     // Default to String
     FieldType::String
 }
 
+/// Whether the arg renders as a checkbox that emits the bare flag when checked
+///
+/// Covers SetTrue/SetFalse switches and any other non-count flag that takes no
+/// value (`num_args(0)`, e.g. `Set` with a `default_missing_value`). Count
+/// flags are value-less too but are handled as counters.
 fn is_bool_arg(arg: &Arg) -> bool {
-    // Check if the action suggests a boolean
-    matches!(arg.get_action(), ArgAction::SetTrue | ArgAction::SetFalse)
-        || arg.get_num_args().map(|n| n.takes_values()).unwrap_or(true) == false
+    match arg.get_action() {
+        ArgAction::SetTrue | ArgAction::SetFalse => true,
+        ArgAction::Count => false,
+        _ => arg.get_num_args().is_some_and(|n| !n.takes_values()),
+    }
+}
+
+/// Whether a value counts as true for clap's boolish parsers
+fn is_truthy(value: &str) -> bool {
+    !matches!(value.to_ascii_lowercase().as_str(), "false" | "no" | "off" | "n" | "f" | "0" | "")
 }
 
 /// Generates HTML for form fields based on field descriptors
@@ -261,134 +1293,597 @@ fn is_bool_arg(arg: &Arg) -> bool {
 /// # Arguments
 /// * `fields` - The field descriptors to generate HTML for
 /// * `prefix` - An optional prefix for field IDs (used for subcommand fields)
-fn generate_form_fields_with_prefix(fields: &[FieldDescriptor], prefix: Option<&str>) -> Markup {
+/// * `config` - The page configuration (for per-field rendering options)
+fn generate_form_fields_with_prefix(fields: &[FieldDescriptor], prefix: Option<&str>, config: &WasmFunctionConfig) -> Markup {
+    // Fields with a help heading render together under it, in place of the
+    // heading's first field; other fields keep their slots
+    let mut sections: Vec<(Option<&str>, Vec<FieldDescriptor>)> = Vec::new();
+    for field in fields {
+        let heading = field.help_heading.as_deref();
+        let section = match heading {
+            Some(_) => sections.iter_mut().find(|(h, _)| *h == heading),
+            None => sections.last_mut().filter(|(h, _)| h.is_none()),
+        };
+        match section {
+            Some((_, members)) => members.push(field.clone()),
+            None => sections.push((heading, vec![field.clone()])),
+        }
+    }
+
     html! {
-        @for field in fields {
-            @let id = if let Some(p) = prefix {
-                format!("{}-{}", p, field.name)
-            } else {
-                field.name.clone()
-            };
+        @for (heading, members) in &sections {
+            @if let Some(heading) = heading {
+                fieldset.heading-group {
+                    legend { (heading) }
+                    (generate_field_section(members, prefix, config))
+                }
+            } @else {
+                (generate_field_section(members, prefix, config))
+            }
+        }
+    }
+}
 
-            // Use help text as label if available and not empty, otherwise use flag/name
-            @let label_text = if !field.help.is_empty() {
-                &field.help
-            } else if field.is_positional {
-                &field.name
-            } else {
-                field.long.as_ref().unwrap_or(&field.name)
-            };
+/// Generates HTML for a run of fields, rendering segmented groups (see
+/// `WasmFunctionConfig::segmented_groups`) in place of their first member
+fn generate_field_section(fields: &[FieldDescriptor], prefix: Option<&str>, config: &WasmFunctionConfig) -> Markup {
+    // Bools in a segmented group render together, in place of the group's
+    // first field in this section
+    let segmented_group = |field: &FieldDescriptor| {
+        if !matches!(field.field_type, FieldType::Bool) {
+            return None;
+        }
+        config.segmented_groups.iter().position(|group| group.contains(&field.name))
+    };
+    let mut rendered_groups = BTreeSet::new();
 
-            // Show flag info as additional context (e.g., "-n, --name" or "--name")
-            @let flag_info = if !field.is_positional {
-                let mut parts = Vec::new();
-                if let Some(s) = field.short {
-                    parts.push(format!("-{}", s));
-                }
-                if let Some(ref l) = field.long {
-                    parts.push(format!("--{}", l));
-                }
-                if !parts.is_empty() {
-                    format!(" ({})", parts.join(", "))
-                } else {
-                    String::new()
+    html! {
+        @for field in fields {
+            @if let Some(group) = segmented_group(field) {
+                @if rendered_groups.insert(group) {
+                    @let members: Vec<&FieldDescriptor> = fields.iter()
+                        .filter(|f| segmented_group(f) == Some(group))
+                        .collect();
+                    (generate_segmented_group(&members, prefix, config))
                 }
-            } else {
-                String::new()
-            };
+            } @else {
+                (generate_form_field(field, prefix, config))
+            }
+        }
+    }
+}
 
-            @let required_marker = if field.required { " *" } else { "" };
-            @let data_field_name = &field.name;
-            @let data_is_positional = field.is_positional.to_string();
-
-            @match &field.field_type {
-                FieldType::String => {
-                    @let default_val = field.default_value.as_deref().unwrap_or("");
-                    // Use textarea for positional string arguments (no short/long flags)
-                    @if field.short.is_none() && field.long.is_none() {
-                        div.field-group
-                            data-field-name=(data_field_name)
-                            data-is-positional=(data_is_positional) {
-                            label for=(id) { (label_text) (required_marker) }
-                            textarea
-                                  id=(id)
-                                  name=(id)
-                                  placeholder=(label_text)
-                                  required[field.required]
-                                  rows="5" { (default_val) }
-                        }
-                    } @else {
-                        div.field-group
-                            data-field-name=(data_field_name)
-                            data-is-positional=(data_is_positional) {
-                            label for=(id) { (label_text) (required_marker) }
-                            @if !flag_info.is_empty() {
-                                span.help-text { (flag_info) }
-                            }
-                            input type="text"
-                                  id=(id)
-                                  name=(id)
-                                  value=(default_val)
-                                  placeholder=(label_text)
-                                  required[field.required];
-                        }
-                    }
-                }
-                FieldType::Bool => {
-                    div.field-group.checkbox-group
-                        data-field-name=(data_field_name)
-                        data-is-positional=(data_is_positional) {
-                        label for=(id) {
-                            input type="checkbox" id=(id) name=(id);
-                            (label_text) (required_marker)
-                        }
-                        @if !flag_info.is_empty() {
-                            span.help-text { (flag_info) }
-                        }
+/// Generates HTML for bool fields rendered as one segmented button bar (see
+/// `WasmFunctionConfig::segmented_groups`)
+///
+/// Each segment is a visually hidden checkbox with a styled label, so the
+/// script reads and sets it like any other bool field.
+fn generate_segmented_group(fields: &[&FieldDescriptor], prefix: Option<&str>, config: &WasmFunctionConfig) -> Markup {
+    html! {
+        div.field-group.segmented-group {
+            div.segmented role="group" {
+                @for field in fields {
+                    @let id = match prefix {
+                        Some(p) => format!("{}-{}", p, field.name),
+                        None => field.name.clone(),
+                    };
+                    @let attrs = FieldDataAttributes::new(field, config);
+                    label.segment
+                        title=[(!field.help.is_empty()).then_some(&field.help)]
+                        data-field-name=(&field.name)
+                        data-field-type=[attrs.field_type] data-default=[attrs.default]
+                        data-required=[attrs.required] data-short=[attrs.short.as_deref()] data-long=[attrs.long] {
+                        input type="checkbox" id=(id) name=(id) checked[bool_default_checked(field)];
+                        span { (field.long.as_ref().unwrap_or(&field.name)) }
                     }
                 }
-                FieldType::Integer => {
-                    @let default_val = field.default_value.as_deref().unwrap_or("0");
-                    div.field-group
-                        data-field-name=(data_field_name)
-                        data-is-positional=(data_is_positional) {
-                        label for=(id) { (label_text) (required_marker) }
-                        @if !flag_info.is_empty() {
-                            span.help-text { (flag_info) }
-                        }
-                        input type="number"
-                              id=(id)
-                              name=(id)
-                              value=(default_val)
-                              required[field.required];
+            }
+        }
+    }
+}
+
+/// Renders label or help text, turning `` `code` `` spans into `<code>`
+/// elements when enabled (see `WasmFunctionConfig::inline_code`). Text with
+/// an unpaired backtick is left as it is.
+fn with_inline_code(text: &str, enabled: bool) -> Markup {
+    let parts: Vec<&str> = text.split('`').collect();
+    if !enabled || parts.len().is_multiple_of(2) {
+        return html! { (text) };
+    }
+
+    html! {
+        @for (i, part) in parts.iter().enumerate() {
+            @if i % 2 == 1 {
+                code { (part) }
+            } @else {
+                (part)
+            }
+        }
+    }
+}
+
+/// Renders help text shown below a field's label
+///
+/// Single-line help is a plain description line. Multi-line help (common in
+/// `long_help`) keeps its structure: runs of `- ` or `* ` lines become a
+/// bullet list, other lines keep their line breaks, and blank lines separate
+/// paragraphs.
+fn help_description(help: &str, inline_code: bool) -> Markup {
+    if !help.contains('\n') {
+        return html! { span.field-description { (with_inline_code(help, inline_code)) } };
+    }
+
+    enum Block<'a> {
+        Lines(Vec<&'a str>),
+        Bullets(Vec<&'a str>),
+    }
+
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut after_blank = false;
+    for line in help.lines().map(str::trim) {
+        if line.is_empty() {
+            after_blank = true;
+            continue;
+        }
+        let bullet = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        match (bullet, blocks.last_mut()) {
+            (Some(item), Some(Block::Bullets(items))) if !after_blank => items.push(item),
+            (Some(item), _) => blocks.push(Block::Bullets(vec![item])),
+            (None, Some(Block::Lines(lines))) if !after_blank => lines.push(line),
+            (None, _) => blocks.push(Block::Lines(vec![line])),
+        }
+        after_blank = false;
+    }
+
+    html! {
+        div.field-description {
+            @for block in &blocks {
+                @match block {
+                    Block::Lines(lines) => {
+                        p {
+                            @for (i, line) in lines.iter().enumerate() {
+                                @if i > 0 { br; }
+                                (with_inline_code(line, inline_code))
+                            }
+                        }
+                    }
+                    Block::Bullets(items) => {
+                        ul {
+                            @for item in items {
+                                li { (with_inline_code(item, inline_code)) }
+                            }
+                        }
                     }
                 }
-                FieldType::Counter => {
-                    @let default_val = field.default_value.as_deref().unwrap_or("0");
-                    div.field-group
-                        data-field-name=(data_field_name)
-                        data-is-positional=(data_is_positional) {
-                        label for=(id) { (label_text) (required_marker) }
-                        span.help-text { (flag_info) " (flag will be repeated N times)" }
+            }
+        }
+    }
+}
+
+/// Formats an enum value for display: capitalized, with hyphens and
+/// underscores as spaces (e.g. "dry-run" -> "Dry run")
+fn format_option_name(value: &str) -> String {
+    let s = value.replace(['-', '_'], " ");
+    let mut c = s.chars();
+    match c.next() {
+        None => String::new(),
+        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+    }
+}
+
+/// Field metadata emitted as `data-*` attributes on the field's group (see
+/// `WasmFunctionConfig::field_data_attributes`); all None when disabled
+#[derive(Default)]
+struct FieldDataAttributes<'a> {
+    field_type: Option<&'static str>,
+    default: Option<&'a str>,
+    required: Option<&'static str>,
+    short: Option<String>,
+    long: Option<&'a str>,
+}
+
+impl<'a> FieldDataAttributes<'a> {
+    fn new(field: &'a FieldDescriptor, config: &WasmFunctionConfig) -> Self {
+        if !config.field_data_attributes {
+            return Self::default();
+        }
+        // Same names as the `type` of the field in the config JSON
+        let field_type = match field.field_type {
+            FieldType::String => "String",
+            FieldType::Bool => "Bool",
+            FieldType::Integer => "Integer",
+            FieldType::Float => "Float",
+            FieldType::Counter => "Counter",
+            FieldType::Enum(_) => "Enum",
+            FieldType::Path => "Path",
+            FieldType::Vec => "Vec",
+            FieldType::IntRange => "IntRange",
+        };
+        Self {
+            field_type: Some(field_type),
+            default: field.default_value.as_deref(),
+            required: Some(if field.required { "true" } else { "false" }),
+            short: field.short.map(String::from),
+            long: field.long.as_deref(),
+        }
+    }
+}
+
+/// The `.field-group` wrapper of a field, with the attributes every field
+/// type shares: its name, whether it's positional and its
+/// `field_data_attributes` metadata
+struct FieldGroup<'a> {
+    field: &'a FieldDescriptor,
+    attrs: &'a FieldDataAttributes<'a>,
+    /// Class naming the kind of field after `field-group`, e.g. "vec-group"
+    kind: Option<&'static str>,
+    /// Whether this is the `primary_field`
+    primary: bool,
+    /// `data-vec-required`, on list fields
+    vec_required: Option<bool>,
+    /// `data-delimiter`, on nested list fields
+    delimiter: Option<&'a str>,
+}
+
+impl<'a> FieldGroup<'a> {
+    fn new(
+        field: &'a FieldDescriptor,
+        attrs: &'a FieldDataAttributes<'a>,
+        kind: Option<&'static str>,
+        primary: bool,
+    ) -> Self {
+        Self { field, attrs, kind, primary, vec_required: None, delimiter: None }
+    }
+
+    /// Renders the wrapper around the field's label and input
+    fn render(&self, body: Markup) -> Markup {
+        let mut class = String::from("field-group");
+        if let Some(kind) = self.kind {
+            class.push(' ');
+            class.push_str(kind);
+        }
+        if self.primary {
+            class.push_str(" primary-field");
+        }
+        let attrs = self.attrs;
+        html! {
+            div class=(class)
+                data-field-name=(&self.field.name)
+                data-is-positional=(self.field.is_positional.to_string())
+                data-field-type=[attrs.field_type] data-default=[attrs.default]
+                data-required=[attrs.required] data-short=[attrs.short.as_deref()] data-long=[attrs.long]
+                data-vec-required=[self.vec_required.map(|required| required.to_string())]
+                data-delimiter=[self.delimiter] {
+                (body)
+            }
+        }
+    }
+}
+
+/// Whether a bool field's checkbox starts out checked
+///
+/// Value-taking bools always emit a value, so the checkbox must start out
+/// matching the default (switches start unchecked).
+fn bool_default_checked(field: &FieldDescriptor) -> bool {
+    field.bool_values.is_some() && field.default_value.as_deref().is_some_and(is_truthy)
+}
+
+/// Generates HTML for a single form field
+fn generate_form_field(field: &FieldDescriptor, prefix: Option<&str>, config: &WasmFunctionConfig) -> Markup {
+    let id = if let Some(p) = prefix {
+        format!("{}-{}", p, field.name)
+    } else {
+        field.name.clone()
+    };
+
+    let name_text = if field.is_positional {
+        &field.name
+    } else {
+        field.long.as_ref().unwrap_or(&field.name)
+    };
+
+    // By default the help text is the label (falling back to the flag/name),
+    // with the long help (or any further lines of multi-line help) below it;
+    // with NameWithDescription the label is always the name and the long help,
+    // or else the help text, goes on a description below it
+    let (label_text, description) = match config.label_style {
+        LabelStyle::HelpAsLabel if !field.help.is_empty() => {
+            let (first_line, rest) = field.help.split_once('\n').unwrap_or((&field.help, ""));
+            let first_line = first_line.trim_end();
+            // The long help usually repeats the help first
+            let below = match field.long_help.as_deref() {
+                Some(long_help) => long_help.strip_prefix(first_line).unwrap_or(long_help),
+                None => rest,
+            };
+            (first_line, Some(below.trim()).filter(|b| !b.is_empty()))
+        }
+        LabelStyle::HelpAsLabel => (name_text.as_str(), field.long_help.as_deref().filter(|h| !h.is_empty())),
+        LabelStyle::NameWithDescription => {
+            let help = field.long_help.as_deref().unwrap_or(&field.help);
+            (name_text.as_str(), Some(help).filter(|h| !h.is_empty()))
+        }
+    };
+
+    // Show flag info as additional context (e.g., "-n, --name" or "--name")
+    let flag_info = if !field.is_positional {
+        let mut parts = Vec::new();
+        if let Some(s) = field.short {
+            parts.push(format!("-{}", s));
+        }
+        if let Some(ref l) = field.long {
+            parts.push(format!("--{}", l));
+        }
+        if !parts.is_empty() {
+            format!(" ({})", parts.join(", "))
+        } else {
+            String::new()
+        }
+    } else {
+        String::new()
+    };
+
+    // Flag info and description rendered below the label
+    let field_info = html! {
+        @if !flag_info.is_empty() {
+            span.help-text { (flag_info) }
+        }
+        @if let Some(description) = description {
+            (help_description(description, config.inline_code))
+        }
+    };
+    let label = with_inline_code(label_text, config.inline_code);
+
+    let required_marker = required_marker(field.required);
+    let attrs = FieldDataAttributes::new(field, config);
+    let is_primary = config.primary_field.as_deref() == Some(field.name.as_str());
+    let group = |kind, body| FieldGroup::new(field, &attrs, kind, is_primary).render(body);
+    let step = config.field_steps.get(&field.name).map(String::as_str);
+    let unit = config.field_units.get(&field.name).map(String::as_str);
+
+    // Resets just this field (clear_buttons); not offered for checkboxes
+    let clear_button = html! {
+        @if config.clear_buttons {
+            button.clear-field-btn type="button" data-field-name=(id) title="Clear" data-i18n-title="clearField" { "×" }
+        }
+    };
+
+    if let Some(nested) = config.nested_fields.get(&field.name) {
+        return generate_nested_vec_field(field, &id, label, field_info, required_marker, &attrs, nested);
+    }
+
+    if let Some(options) = config.checkbox_sets.get(&field.name) {
+        return generate_checkbox_set_field(field, &id, label, field_info, required_marker, &attrs, options);
+    }
+
+    html! {
+        @match &field.field_type {
+            FieldType::String => {
+                @let default_val = field.default_value.as_deref().unwrap_or("");
+                @let uuid = config.uuid_inputs && field.is_uuid;
+                @let hint_input = if uuid { Some(("text", UUID_PLACEHOLDER)) } else { value_hint_input(field) };
+                // Use textarea for positional string arguments (no short/long
+                // flags), unless a value hint calls for a specific input or
+                // the form is a single line, and for fields configured as one
+                @let positional_text = field.short.is_none() && field.long.is_none()
+                    && hint_input.is_none() && !config.is_inline();
+                @if positional_text || config.textarea_fields.contains(&field.name) {
+                    (group(None, html! {
+                        label for=(id) { (label) (required_marker) }
+                        (clear_button)
+                        (field_info)
+                        textarea
+                              id=(id)
+                              name=(id)
+                              placeholder=(if field.non_utf8_default { NON_UTF8_DEFAULT_PLACEHOLDER } else { label_text })
+                              data-i18n=[field.non_utf8_default.then_some("nonUtf8Default")]
+                              required[field.required]
+                              autofocus[is_primary]
+                              rows="5" { (default_val) }
+                    }))
+                } @else {
+                    (group(None, html! {
+                        label for=(id) { (label) (required_marker) }
+                        (clear_button)
+                        (field_info)
+                        input type=(hint_input.map_or("text", |(t, _)| t))
+                              id=(id)
+                              name=(id)
+                              value=(default_val)
+                              placeholder=(if field.non_utf8_default {
+                                  NON_UTF8_DEFAULT_PLACEHOLDER
+                              } else {
+                                  hint_input.map_or(label_text, |(_, p)| p)
+                              })
+                              data-i18n=[field.non_utf8_default.then_some("nonUtf8Default")]
+                              pattern=[uuid.then_some(UUID_PATTERN)]
+                              title=[uuid.then_some(UUID_TITLE)]
+                              required[field.required]
+                              autofocus[is_primary];
+                    }))
+                }
+            }
+            FieldType::Bool => {
+                @let checked = bool_default_checked(field);
+                (group(Some("checkbox-group"), html! {
+                    label for=(id) {
+                        input type="checkbox" id=(id) name=(id) checked[checked] autofocus[is_primary];
+                        (label) (required_marker)
+                    }
+                    (field_info)
+                }))
+            }
+            FieldType::Integer => {
+                @let default_val = field.default_value.as_deref().unwrap_or("0");
+                (group(None, html! {
+                    label for=(id) { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    (with_unit(html! {
+                        input type="number"
+                              id=(id)
+                              name=(id)
+                              value=(default_val)
+                              step=[step]
+                              required[field.required]
+                              autofocus[is_primary];
+                    }, unit))
+                }))
+            }
+            FieldType::Float => {
+                @let default_val = field.default_value.as_deref().unwrap_or("0");
+                (group(None, html! {
+                    label for=(id) { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    (with_unit(html! {
                         input type="number"
                               id=(id)
                               name=(id)
                               value=(default_val)
-                              min="0"
+                              step=(step.unwrap_or("any"))
+                              required[field.required]
+                              autofocus[is_primary];
+                    }, unit))
+                }))
+            }
+            FieldType::IntRange => {
+                @let separator = config.int_ranges.get(&field.name).map_or("..", String::as_str);
+                @let (start, end) = field.default_value.as_deref()
+                    .and_then(|d| d.split_once(separator))
+                    .unwrap_or(("", ""));
+                (group(None, html! {
+                    label for=(format!("{}-start", id)) { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    // Given a select-like value by initIntRangeFields
+                    div.int-range id=(id) data-separator=(separator) {
+                        input type="number"
+                              id=(format!("{}-start", id))
+                              name=(format!("{}-start", id))
+                              value=(start)
+                              step="1"
+                              placeholder="Start"
+                              data-i18n="rangeStart"
+                              required[field.required]
+                              autofocus[is_primary];
+                        span.int-range-separator { (separator) }
+                        input type="number"
+                              id=(format!("{}-end", id))
+                              name=(format!("{}-end", id))
+                              value=(end)
+                              step="1"
+                              placeholder="End"
+                              data-i18n="rangeEnd"
                               required[field.required];
                     }
-                }
-                FieldType::Enum(options) => {
-                    @let default_val = field.default_value.as_deref().unwrap_or("");
-                    div.field-group
-                        data-field-name=(data_field_name)
-                        data-is-positional=(data_is_positional) {
-                        label for=(id) { (label_text) (required_marker) }
-                        @if !flag_info.is_empty() {
-                            span.help-text { (flag_info) }
+                }))
+            }
+            FieldType::Counter if config.counter_levels.get(&field.name).is_some_and(|levels| !levels.is_empty()) => {
+                // Level N repeats the flag N times; a default beyond the last
+                // level selects the last
+                @let levels = &config.counter_levels[&field.name];
+                @let default_level = field.default_value.as_deref()
+                    .and_then(|d| d.parse::<usize>().ok())
+                    .unwrap_or(0)
+                    .min(levels.len() - 1);
+                (group(None, html! {
+                    label { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    // Read and set like a dropdown (see initEnumButtons)
+                    div.enum-buttons.counter-levels id=(id) role="radiogroup" {
+                        @for (count, level) in levels.iter().enumerate() {
+                            label.enum-button {
+                                input type="radio" name=(id) value=(count) checked[count == default_level];
+                                span { (level) }
+                            }
+                        }
+                    }
+                }))
+            }
+            FieldType::Counter => {
+                @let default_val = field.default_value.as_deref().unwrap_or("0");
+                (group(None, html! {
+                    label for=(id) { (label) (required_marker) }
+                    (clear_button)
+                    span.help-text { (flag_info) " (flag will be repeated N times)" }
+                    @if let Some(description) = description {
+                        (help_description(description, config.inline_code))
+                    }
+                    input type="number"
+                          id=(id)
+                          name=(id)
+                          value=(default_val)
+                          min="0"
+                          required[field.required]
+                          autofocus[is_primary];
+                }))
+            }
+            FieldType::Enum(_) if field.hide_possible_values => {
+                // Free text so the hidden values aren't listed
+                @let default_val = field.default_value.as_deref().unwrap_or("");
+                (group(None, html! {
+                    label for=(id) { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    input type="text"
+                          id=(id)
+                          name=(id)
+                          value=(default_val)
+                          placeholder=(label_text)
+                          required[field.required]
+                          autofocus[is_primary];
+                }))
+            }
+            FieldType::Enum(options) => {
+                // An explicit default, even an empty one (`default_value = ""`),
+                // preselects its option; otherwise the placeholder stands for
+                // "not given" and clap applies whatever default it has
+                @let is_default = |value: &str| field.default_value.as_deref().is_some_and(|default_val| {
+                    if field.ignore_case {
+                        value.eq_ignore_ascii_case(default_val)
+                    } else {
+                        value == default_val
+                    }
+                });
+                @let has_default_option = options.iter().any(|opt| is_default(&opt.value));
+                @if config.enum_buttons.is_some_and(|max| options.len() <= max) {
+                    // One radio-styled button per option; the help text is the tooltip
+                    (group(None, html! {
+                        label { (label) (required_marker) }
+                        (clear_button)
+                        (field_info)
+                        div.enum-buttons id=(id) role="radiogroup" {
+                            @if !field.required && !has_default_option {
+                                label.enum-button {
+                                    input type="radio" name=(id) value="" checked;
+                                    span data-i18n="noneOption" { "None" }
+                                }
+                            }
+                            @for opt in options {
+                                label.enum-button title=[(!opt.help.is_empty()).then_some(&opt.help)] {
+                                    input type="radio"
+                                          name=(id)
+                                          value=(&opt.value)
+                                          checked[is_default(&opt.value)]
+                                          required[field.required];
+                                    span {
+                                        @if let Some(ref icon) = opt.icon {
+                                            i.enum-icon aria-hidden="true" { (icon) }
+                                        }
+                                        (format_option_name(&opt.value))
+                                    }
+                                }
+                            }
                         }
-                        select id=(id) name=(id) required[field.required] {
-                            @if !field.required && default_val.is_empty() {
+                    }))
+                } @else {
+                    (group(None, html! {
+                        label for=(id) { (label) (required_marker) }
+                        (clear_button)
+                        (field_info)
+                        select id=(id) name=(id) required[field.required] autofocus[is_primary] {
+                            @if !field.required && !has_default_option {
                                 option value="" selected data-i18n="selectOption" { "-- Select an option --" }
                             }
                             @for opt in options {
@@ -396,85 +1891,406 @@ fn generate_form_fields_with_prefix(fields: &[FieldDescriptor], prefix: Option<&
                                 @let display_text = if !opt.help.is_empty() {
                                     format!("{} ({})", opt.help, opt.value)
                                 } else {
-                                    // Format option display: capitalize and replace hyphens/underscores with spaces
-                                    let s = opt.value.replace('-', " ").replace('_', " ");
-                                    let mut c = s.chars();
-                                    match c.next() {
-                                        None => String::new(),
-                                        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-                                    }
+                                    format_option_name(&opt.value)
+                                };
+                                @let display_text = match opt.icon {
+                                    Some(ref icon) => format!("{} {}", icon, display_text),
+                                    None => display_text,
                                 };
-                                @if &opt.value == default_val {
+                                @if is_default(&opt.value) {
                                     option value=(&opt.value) selected { (display_text) }
                                 } @else {
                                     option value=(&opt.value) { (display_text) }
                                 }
                             }
                         }
-                    }
+                    }))
                 }
-                FieldType::Vec => {
-                    div.field-group.vec-group
-                        data-field-name=(data_field_name)
-                        data-is-positional=(data_is_positional)
-                        data-vec-required=(field.required.to_string()) {
-                        label for=(id) { (label_text) (required_marker) }
-                        @if !flag_info.is_empty() {
-                            span.help-text { (flag_info) }
-                        }
-                        div.vec-container id=(format!("{}-container", id)) {
-                            input.vec-input
-                                  type="text"
-                                  placeholder="Enter value and press Enter"
-                                  data-i18n="enterValuePlaceholder"
-                                  data-field-name=(id);
-                            div.vec-items id=(format!("{}-items", id)) {}
+            }
+            FieldType::Path => {
+                @let default_val = field.default_value.as_deref().unwrap_or("");
+                (group(Some("path-field"), html! {
+                    label for=(id) { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    div.path-input {
+                        input type="text"
+                              id=(id)
+                              name=(id)
+                              value=(default_val)
+                              placeholder=(if field.non_utf8_default { NON_UTF8_DEFAULT_PLACEHOLDER } else { "path/to/file" })
+                              data-i18n=(if field.non_utf8_default { "nonUtf8Default" } else { "pathPlaceholder" })
+                              required[field.required]
+                              autofocus[is_primary];
+                        // The WASM build has no file system, so picking a file
+                        // only fills in its name
+                        label.path-browse {
+                            span data-i18n="browse" { "Browse…" }
+                            input.path-file-input type="file" data-field-name=(id);
                         }
                     }
+                }))
+            }
+            FieldType::Vec => {
+                (FieldGroup {
+                    vec_required: Some(field.required),
+                    ..FieldGroup::new(field, &attrs, Some("vec-group"), is_primary)
+                }.render(html! {
+                    label for=(id) { (label) (required_marker) }
+                    (clear_button)
+                    (field_info)
+                    div.vec-container id=(format!("{}-container", id)) {
+                        input.vec-input
+                              type="text"
+                              id=(id)
+                              placeholder="Enter value and press Enter"
+                              data-i18n="enterValuePlaceholder"
+                              data-field-name=(id)
+                              autofocus[is_primary];
+                        div.vec-items id=(format!("{}-items", id)) {}
+                    }
+                }))
+            }
+        }
+    }
+}
+
+/// Generates HTML for a field rendered as a fixed set of checkboxes (see
+/// `WasmFunctionConfig::checkbox_sets`)
+fn generate_checkbox_set_field(
+    field: &FieldDescriptor,
+    id: &str,
+    label: Markup,
+    field_info: Markup,
+    required_marker: Markup,
+    attrs: &FieldDataAttributes,
+    options: &[CheckboxOption],
+) -> Markup {
+    FieldGroup::new(field, attrs, Some("checkbox-set-field"), false).render(html! {
+        label { (label) (required_marker) }
+        (field_info)
+        div.checkbox-set id=(id) {
+            @for (i, option) in options.iter().enumerate() {
+                label.checkbox-set-option {
+                    input type="checkbox"
+                          id=(format!("{}-{}", id, i))
+                          value=(&option.value)
+                          checked[field.default_value.as_deref() == Some(option.value.as_str())];
+                    (option.label)
                 }
             }
         }
+    })
+}
+
+/// The "*" appended to a required field's label, with text for screen readers
+/// (the asterisk itself is hidden from them)
+fn required_marker(required: bool) -> Markup {
+    html! {
+        @if required {
+            " "
+            span.required-marker aria-hidden="true" { "*" }
+            span.sr-only data-i18n="requiredField" { "Required field" }
+        }
+    }
+}
+
+/// Wraps a number input with a non-editable unit suffix, if there is one
+fn with_unit(input: Markup, unit: Option<&str>) -> Markup {
+    html! {
+        @if let Some(unit) = unit {
+            div.input-with-unit {
+                (input)
+                span.input-unit { (unit) }
+            }
+        } @else {
+            (input)
+        }
+    }
+}
+
+/// Generates HTML for a field that collects groups of values (see `NestedVecField`)
+fn generate_nested_vec_field(
+    field: &FieldDescriptor,
+    id: &str,
+    label: Markup,
+    field_info: Markup,
+    required_marker: Markup,
+    attrs: &FieldDataAttributes,
+    nested: &NestedVecField,
+) -> Markup {
+    FieldGroup {
+        delimiter: nested.delimiter.as_deref(),
+        ..FieldGroup::new(field, attrs, Some("nested-vec-field"), false)
+    }.render(html! {
+        label for=(id) { (label) (required_marker) }
+        (field_info)
+        div.vec-container id=(format!("{}-container", id)) {
+            div.nested-vec-groups id=(format!("{}-groups", id)) {}
+            button.add-group-btn type="button" id=(id) data-field-name=(id) data-i18n="addGroup" { "Add group" }
+        }
+    })
+}
+
+/// Generates HTML for form fields, grouping them according to the config
+///
+/// With `collapse_optional_fields`, only fields the user must fill in (required
+/// and without a default) are shown directly; the rest go into a collapsed
+/// `<details>` section. Fields listed in `advanced_fields` always go there.
+/// Collapsed inputs stay in the form, so their defaults are still submitted.
+fn generate_form_fields(fields: &[FieldDescriptor], prefix: Option<&str>, config: &WasmFunctionConfig) -> Markup {
+    // The primary field always renders first
+    let is_primary = |f: &FieldDescriptor| config.primary_field.as_deref() == Some(f.name.as_str());
+    let mut fields = fields.to_vec();
+
+    if config.field_order != FieldOrder::Declaration {
+        // Positionals keep their slots since their order is meaningful; flags
+        // are sorted into the remaining slots
+        let mut flags: Vec<FieldDescriptor> = fields.iter().filter(|f| !f.is_positional).cloned().collect();
+        match config.field_order {
+            FieldOrder::Alphabetical => flags.sort_by(|a, b| a.name.cmp(&b.name)),
+            // Stable, so equal orders keep their declaration order
+            FieldOrder::DisplayOrder => flags.sort_by_key(|f| f.display_order.unwrap_or(usize::MAX)),
+            FieldOrder::Declaration => {}
+        }
+        for (slot, flag) in fields.iter_mut().filter(|f| !f.is_positional).zip(flags) {
+            *slot = flag;
+        }
+    }
+
+    // Positionals render in command-line order (by clap index) within the
+    // positional slots; flags keep theirs. Positionals without an index (from
+    // hand-written configs) keep their relative order at the end.
+    let mut positionals: Vec<FieldDescriptor> = fields.iter().filter(|f| f.is_positional).cloned().collect();
+    positionals.sort_by_key(|f| f.index.unwrap_or(usize::MAX));
+    for (slot, positional) in fields.iter_mut().filter(|f| f.is_positional).zip(positionals) {
+        *slot = positional;
+    }
+
+    fields.sort_by_key(|f| !is_primary(f));
+
+    // Advanced fields are collapsed whatever their other settings, after any
+    // collapsed optional fields
+    let (advanced, fields): (Vec<FieldDescriptor>, Vec<FieldDescriptor>) = fields
+        .into_iter()
+        .partition(|f| config.advanced_fields.contains(&f.name));
+
+    let (essential, mut collapsed): (Vec<FieldDescriptor>, Vec<FieldDescriptor>) = if config.collapse_optional_fields {
+        fields.into_iter().partition(|f| (f.required && f.default_value.is_none()) || is_primary(f))
+    } else {
+        (fields, Vec::new())
+    };
+    collapsed.extend(advanced);
+
+    html! {
+        (generate_form_fields_with_prefix(&essential, prefix, config))
+        @if !collapsed.is_empty() {
+            details.advanced-options {
+                summary data-i18n="advancedOptions" { "Advanced options" }
+                (generate_form_fields_with_prefix(&collapsed, prefix, config))
+            }
+        }
+    }
+}
+
+/// Selector value of the free-form subcommand offered for commands that
+/// allow external subcommands
+const EXTERNAL_SUBCOMMAND: &str = "__external__";
+
+/// Id prefix of the section holding the fields every subcommand shares
+/// (`shared_subcommand_fields`)
+const SHARED_FIELDS: &str = "__shared__";
+
+/// Flags that every subcommand has with identical descriptors; none with
+/// fewer than two subcommands. Positionals are left out, since they're
+/// ordered with each subcommand's own positionals.
+fn shared_subcommand_fields(subcommands: &[SubcommandDescriptor]) -> Vec<FieldDescriptor> {
+    let [first, rest @ ..] = subcommands else {
+        return Vec::new();
+    };
+    if rest.is_empty() {
+        return Vec::new();
+    }
+
+    let same = |a: &FieldDescriptor, b: &FieldDescriptor| {
+        a.name == b.name && serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+    };
+    first.fields.iter()
+        .filter(|f| !f.is_positional)
+        .filter(|f| rest.iter().all(|sc| sc.fields.iter().any(|g| same(f, g))))
+        .cloned()
+        .collect()
+}
+
+/// Generates the selector option for a subcommand
+fn subcommand_option(subcmd: &SubcommandDescriptor) -> Markup {
+    // Show aliases next to the canonical name; the value stays canonical
+    let names = if subcmd.aliases.is_empty() {
+        subcmd.name.clone()
+    } else {
+        format!("{}, aliases: {}", subcmd.name, subcmd.aliases.join(", "))
+    };
+    let display_text = if !subcmd.help.is_empty() {
+        format!("{} ({})", subcmd.help, names)
+    } else {
+        names
+    };
+
+    html! {
+        option value=(&subcmd.name) { (display_text) }
+    }
+}
+
+/// A subcommand's tab for `SubcommandStyle::Tabs`; only the first tab is in
+/// the tab order until one is selected
+fn subcommand_tab(name: &str, help: &str, first: bool) -> Markup {
+    html! {
+        button.subcommand-tab type="button" role="tab"
+            id=(format!("subcommand-tab-{}", name))
+            aria-controls=(format!("subcommand-{}", name))
+            aria-selected="false"
+            tabindex=(if first { "0" } else { "-1" })
+            data-subcommand=(name)
+            title=[(!help.is_empty()).then_some(help)] {
+            @if name == EXTERNAL_SUBCOMMAND {
+                span data-i18n="externalSubcommand" { "Other (custom)" }
+            } @else {
+                (name)
+            }
+        }
     }
 }
 
-/// Generates HTML for form fields (wrapper for backwards compatibility)
-fn generate_form_fields(fields: &[FieldDescriptor]) -> Markup {
-    generate_form_fields_with_prefix(fields, None)
+/// Groups subcommands by their configured category
+///
+/// Uncategorized subcommands come first (with no category), followed by one
+/// group per category in order of first appearance.
+fn group_subcommands_by_category<'a>(
+    subcommands: &'a [SubcommandDescriptor],
+    config: &'a WasmFunctionConfig,
+) -> Vec<(Option<&'a str>, Vec<&'a SubcommandDescriptor>)> {
+    let mut groups: Vec<(Option<&str>, Vec<&SubcommandDescriptor>)> = vec![(None, Vec::new())];
+    for subcmd in subcommands {
+        let category = config.subcommand_categories.get(&subcmd.name).map(String::as_str);
+        match groups.iter_mut().find(|(c, _)| *c == category) {
+            Some((_, group)) => group.push(subcmd),
+            None => groups.push((category, vec![subcmd])),
+        }
+    }
+    groups.retain(|(_, group)| !group.is_empty());
+    groups
 }
 
 /// Generates HTML for subcommand selector and fields
-fn generate_subcommand_sections(subcommands: &[SubcommandDescriptor]) -> Markup {
+fn generate_subcommand_sections(subcommands: &[SubcommandDescriptor], config: &WasmFunctionConfig) -> Markup {
+    let shared = if config.shared_subcommand_fields {
+        shared_subcommand_fields(subcommands)
+    } else {
+        Vec::new()
+    };
+
+    let tabs = config.subcommand_style == SubcommandStyle::Tabs;
+    // Tab panels are labelled by their tab
+    let tab_id = |name: &str| tabs.then(|| format!("subcommand-tab-{}", name));
+
     html! {
-        @if !subcommands.is_empty() {
+        @if !subcommands.is_empty() || config.allow_external_subcommands {
             div.form-section.subcommand-section {
                 h2 data-i18n="subcommands" { "Subcommands" }
-                div.field-group {
-                    label for="subcommand-selector" data-i18n="selectSubcommand" { "Select Subcommand" }
-                    select #subcommand-selector name="subcommand" {
-                        option value="" selected data-i18n="selectSubcommandPlaceholder" { "-- Select a subcommand --" }
-                        @for subcmd in subcommands {
-                            @let display_text = if !subcmd.help.is_empty() {
-                                format!("{} ({})", subcmd.help, subcmd.name)
-                            } else {
-                                subcmd.name.clone()
-                            };
-                            option value=(&subcmd.name) { (display_text) }
+                @if config.subcommand_required {
+                    p.subcommand-hint data-i18n="chooseOneSubcommand" { "Choose one:" }
+                }
+                @if tabs {
+                    // Has a select-like `value` (see initSubcommandTabs), so
+                    // it stands in for the dropdown
+                    div #subcommand-selector.subcommand-tabs role="tablist" aria-label="Subcommands" {
+                        @for (i, subcmd) in group_subcommands_by_category(subcommands, config)
+                            .into_iter().flat_map(|(_, group)| group).enumerate() {
+                            (subcommand_tab(&subcmd.name, &subcmd.help, i == 0))
+                        }
+                        @if config.allow_external_subcommands {
+                            (subcommand_tab(EXTERNAL_SUBCOMMAND, "", subcommands.is_empty()))
+                        }
+                    }
+                } @else {
+                    div.field-group {
+                        label for="subcommand-selector" {
+                            span data-i18n="selectSubcommand" { "Select Subcommand" }
+                            (required_marker(config.subcommand_required))
+                        }
+                        select #subcommand-selector name="subcommand" required[config.subcommand_required] {
+                            option value="" selected data-i18n="selectSubcommandPlaceholder" { "-- Select a subcommand --" }
+                            @for (category, group) in group_subcommands_by_category(subcommands, config) {
+                                @if let Some(category) = category {
+                                    optgroup label=(category) {
+                                        @for subcmd in group {
+                                            (subcommand_option(subcmd))
+                                        }
+                                    }
+                                } @else {
+                                    @for subcmd in group {
+                                        (subcommand_option(subcmd))
+                                    }
+                                }
+                            }
+                            @if config.allow_external_subcommands {
+                                option value=(EXTERNAL_SUBCOMMAND) data-i18n="externalSubcommand" { "Other (custom)" }
+                            }
                         }
                     }
                 }
 
+                @if !shared.is_empty() {
+                    // Shown along with whichever subcommand is selected
+                    div.subcommand-fields
+                        id=(format!("subcommand-{}", SHARED_FIELDS))
+                        data-subcommand=(SHARED_FIELDS)
+                        hidden {
+                        h3 data-i18n="sharedOptions" { "Options for every subcommand" }
+                        (generate_form_fields(&shared, Some(SHARED_FIELDS), config))
+                    }
+                }
+
                 @for subcmd in subcommands {
+                    @let fields: Vec<FieldDescriptor> = subcmd.fields.iter()
+                        .filter(|f| !shared.iter().any(|s| s.name == f.name))
+                        .cloned()
+                        .collect();
                     div.subcommand-fields
                         id=(format!("subcommand-{}", subcmd.name))
                         data-subcommand=(&subcmd.name)
-                        style="display: none;" {
+                        role=[tabs.then_some("tabpanel")]
+                        aria-labelledby=[tab_id(&subcmd.name)]
+                        hidden {
                         @let header_text = if !subcmd.help.is_empty() {
                             format!("{} ({})", subcmd.help, subcmd.name)
                         } else {
                             format!("Options for '{}'", subcmd.name)
                         };
                         h3 { (header_text) }
-                        (generate_form_fields_with_prefix(&subcmd.fields, Some(&subcmd.name)))
+                        (generate_form_fields(&fields, Some(&subcmd.name), config))
+                    }
+                }
+
+                @if config.allow_external_subcommands {
+                    // Any other subcommand name; the rest of the command line goes to it
+                    div.subcommand-fields
+                        id=(format!("subcommand-{}", EXTERNAL_SUBCOMMAND))
+                        data-subcommand=(EXTERNAL_SUBCOMMAND)
+                        role=[tabs.then_some("tabpanel")]
+                        aria-labelledby=[tab_id(EXTERNAL_SUBCOMMAND)]
+                        hidden {
+                        div.field-group {
+                            label for="external-subcommand-name" data-i18n="externalSubcommandName" { "Subcommand name" }
+                            input type="text" #external-subcommand-name name="external-subcommand-name" required;
+                        }
+                        div.field-group {
+                            label for="external-subcommand-args" data-i18n="externalSubcommandArgs" { "Arguments" }
+                            span.help-text data-i18n="extraArgsHelp" {
+                                "Appended to the command line as-is; use quotes for values with spaces"
+                            }
+                            textarea #external-subcommand-args name="external-subcommand-args" rows="2" {}
+                        }
                     }
                 }
             }
@@ -482,64 +2298,159 @@ fn generate_subcommand_sections(subcommands: &[SubcommandDescriptor]) -> Markup
     }
 }
 
-/// Helper function to generate CSS styles
-/// The CSS styles are loaded from cli-ui.css for better readability
-fn generate_styles() -> Markup {
-    // Load the CSS from the separate file at compile time
-    const CSS_CONTENT: &str = include_str!("cli-ui.css");
-
+/// Generates HTML for the example invocation chips
+fn generate_examples_section(examples: &[String]) -> Markup {
     html! {
-        style {
-            (PreEscaped(CSS_CONTENT))
+        @if !examples.is_empty() {
+            div.examples-section {
+                label data-i18n="examples" { "Examples:" }
+                div.example-chips {
+                    @for example in examples {
+                        button.example-chip type="button" data-command=(example) { (example) }
+                    }
+                }
+            }
         }
     }
 }
 
-/// Helper function to generate JavaScript
-/// The main JavaScript code is loaded from cli-ui.js for better readability
-fn generate_script(function_name: &str, package_name: &str, fields_json: &str, subcommands_json: &str) -> Markup {
+/// The stylesheet of the generated pages (`cli-ui.css`), for writing to the
+/// file that `external_css` points at
+pub const CLI_UI_CSS: &str = include_str!("cli-ui.css");
+
+/// The form-to-arguments logic of the generated pages (`arg-builder.js`)
+const ARG_BUILDER_JS: &str = include_str!("arg-builder.js");
+
+/// The translations script of the generated pages (`i18n.js`), for writing to
+/// the file that `ExternalScripts::i18n` points at
+pub const I18N_JS: &str = include_str!("i18n.js");
+
+/// Returns the page's own script (`cli-ui.js` set up for this config), for
+/// writing to the file that `ExternalScripts::page` points at
+///
+/// # Example
+///
+/// ```
+/// use clap_web_code_gen::{page_script, WasmFunctionConfig};
+///
+/// let config = WasmFunctionConfig {
+///     function_name: "run_bind".to_string(),
+///     package_name: "my-tool".to_string(),
+///     ..Default::default()
+/// };
+/// assert!(page_script(&config).contains("const WASM_IMPORT_PATH = './my_tool.js';"));
+/// ```
+pub fn page_script(config: &WasmFunctionConfig) -> String {
     // Load the JavaScript template from the separate file at compile time
     const JS_TEMPLATE: &str = include_str!("cli-ui.js");
-    const I18N_JS: &str = include_str!("i18n.js");
-
-    // Generate the configuration script (dynamic data only)
-    let config_script = format!(
-        r#"window.CLI_CONFIG = {{ fields: {}, subcommands: {} }};"#,
-        fields_json,
-        subcommands_json
-    );
 
     // Convert package name to valid JavaScript module name (hyphens -> underscores)
     // wasm-pack converts package names like "rhyme-checker" to "rhyme_checker" in file names
-    let js_package_name = package_name.replace('-', "_");
+    let js_package_name = config.package_name.replace('-', "_");
 
     // Replace placeholders in the JavaScript template with actual values
     // Since HTML is now in pkg/, import is relative to pkg/ directory
-    let main_script = JS_TEMPLATE
-        .replace("[FUNCTION_NAME]", function_name)
-        .replace("[IMPORT_PATH]", &format!("./{}.js", js_package_name));
-
-    html! {
-        // First script: i18n support
-        script {
-            (PreEscaped(I18N_JS))
-        }
-        // Second script: Set up configuration (inline)
-        script {
-            (PreEscaped(config_script))
-        }
-        // Third script: Main application logic (from cli-ui.js)
-        script type="module" {
-            (PreEscaped(main_script))
-        }
-    }
+    JS_TEMPLATE
+        .replace("[FUNCTION_NAME]", &config.function_name)
+        .replace("[IMPORT_PATH]", &format!("./{}.js", js_package_name))
+        .replace("[CONFIG_PATH]", config.external_config.as_deref().unwrap_or_default())
+        .replace("[CONFIG_MODULE]", config.config_module.as_deref().unwrap_or_default())
 }
 
-/// Generates a static HTML page for interacting with a WASM-bound Rust function
+/// Returns the script that turns a generated form into the WASM function's
+/// arguments, for frontends that build their own page around the generated
+/// fields (see `render_fields_html`)
 ///
-/// # Arguments
+/// It's a plain (non-module) script defining
+/// `window.buildCliArgs(config, formElement)`, which returns the argument list
+/// for the form's current state, given the page config (`config_to_json`).
+/// Generated pages embed the same script and build their arguments with it.
 ///
-/// * `config` - Configuration specifying the WASM function details
+/// # Example
+///
+/// ```
+/// use clap_web_code_gen::arg_builder_js;
+///
+/// assert!(arg_builder_js().contains("window.buildCliArgs"));
+/// ```
+pub fn arg_builder_js() -> &'static str {
+    ARG_BUILDER_JS
+}
+
+/// Helper function to generate CSS styles
+/// The CSS styles are loaded from cli-ui.css for better readability.
+/// With a `css_path`, the page links the stylesheet there instead of inlining it.
+fn generate_styles(css_path: Option<&str>, nonce: Option<&str>) -> Markup {
+    html! {
+        @if let Some(href) = css_path {
+            link rel="stylesheet" href=(href) nonce=[nonce];
+        } @else {
+            style nonce=[nonce] {
+                (PreEscaped(CLI_UI_CSS))
+            }
+        }
+    }
+}
+
+/// Helper function to generate JavaScript
+/// The main JavaScript code is loaded from cli-ui.js for better readability.
+/// With `external_config`, the config is fetched from there instead of
+/// inlined, with `config_module` it's imported from that ES module, and with
+/// `external_scripts` the scripts are loaded from their files.
+fn generate_script(config: &WasmFunctionConfig, config_json: &str, nonce: Option<&str>) -> Markup {
+    // Generate the configuration script (dynamic data only)
+    // Escape "</" so help text can't close the inline script early
+    let config_script = format!(
+        r#"window.CLI_CONFIG = {};"#,
+        config_json.replace("</", "<\\/")
+    );
+
+    let inline_config = config.external_config.is_none() && config.config_module.is_none();
+
+    if let Some(ref scripts) = config.external_scripts {
+        return html! {
+            script src=(scripts.i18n) nonce=[nonce] {}
+            script src=(scripts.arg_builder) nonce=[nonce] {}
+            @if inline_config {
+                script nonce=[nonce] {
+                    (PreEscaped(config_script))
+                }
+            }
+            script type="module" src=(scripts.page) nonce=[nonce] {}
+        };
+    }
+
+    html! {
+        // First script: i18n support
+        script nonce=[nonce] {
+            (PreEscaped(I18N_JS))
+        }
+        // Argument assembly, shared with other frontends (arg_builder_js)
+        script nonce=[nonce] {
+            (PreEscaped(ARG_BUILDER_JS))
+        }
+        // Set up configuration (inline, unless fetched or imported by
+        // cli-ui.js)
+        @if inline_config {
+            script nonce=[nonce] {
+                (PreEscaped(config_script))
+            }
+        }
+        // Main application logic (from cli-ui.js)
+        script type="module" nonce=[nonce] {
+            (PreEscaped(page_script(config)))
+        }
+    }
+}
+
+/// Generates a static HTML page for interacting with a WASM-bound Rust function
+///
+/// The config isn't validated here; call `WasmFunctionConfig::validate` first
+/// for configs you build or adjust yourself.
+///
+/// # Arguments
+///
+/// * `config` - Configuration specifying the WASM function details
 ///
 /// # Returns
 ///
@@ -547,8 +2458,8 @@ fn generate_script(function_name: &str, package_name: &str, fields_json: &str, s
 ///
 /// # Example
 ///
-/// ```
-/// use code_gen::{generate_wasm_function_page, WasmFunctionConfig, FieldDescriptor, FieldType};
+/// ```no_run
+/// use clap_web_code_gen::{generate_wasm_function_page, WasmFunctionConfig, FieldDescriptor, FieldType};
 ///
 /// let config = WasmFunctionConfig {
 ///     function_name: "process".to_string(),
@@ -565,74 +2476,520 @@ fn generate_script(function_name: &str, package_name: &str, fields_json: &str, s
 ///             default_value: None,
 ///             required: true,
 ///             is_positional: false,
+///             ..Default::default()
 ///         }
 ///     ],
 ///     subcommands: vec![],
+///     ..Default::default()
 /// };
 ///
 /// let html = generate_wasm_function_page(&config);
 /// std::fs::write("output.html", html).unwrap();
 /// ```
 pub fn generate_wasm_function_page(config: &WasmFunctionConfig) -> String {
-    let form_fields = generate_form_fields(&config.fields);
-    let subcommand_sections = generate_subcommand_sections(&config.subcommands);
-    let fields_json = serde_json::to_string(&config.fields).unwrap_or_else(|_| "[]".to_string());
-    let subcommands_json = serde_json::to_string(&config.subcommands).unwrap_or_else(|_| "[]".to_string());
+    let config = &*config.with_field_overrides();
+    let nonce = config.script_nonce.as_deref();
+    let styles = if config.bare {
+        html! {}
+    } else {
+        generate_styles(config.external_css.as_deref(), nonce)
+    };
+    let form = generate_page_content(config);
+    let script = generate_script(config, &config_to_json(config), nonce);
+
+    if config.web_component {
+        return generate_web_component(styles, form, script, nonce).into_string();
+    }
+
+    // Trusted, so inserted verbatim
+    let head_extra = PreEscaped(config.head_extra.as_deref().unwrap_or(""));
+
+    if let Some(ref shell) = config.html_shell {
+        let title = html! { (config.page_title) };
+        return fill_html_shell(shell, &[
+            ("TITLE", &title.into_string()),
+            ("META", &generate_meta_tags(config).into_string()),
+            ("STYLES", &html! { (styles) (head_extra) }.into_string()),
+            ("FORM", &form.into_string()),
+            ("SCRIPT", &script.into_string()),
+        ]);
+    }
 
     let page = html! {
         (DOCTYPE)
-        html {
+        html dir=[config.direction.dir_attr()] {
             head {
                 meta charset="UTF-8";
                 meta name="viewport" content="width=device-width, initial-scale=1.0";
                 title { (config.page_title) }
-                (generate_styles())
+                (generate_meta_tags(config))
+                (styles)
+                (head_extra)
             }
             body {
-                div .container {
-                    div .header-row {
-                        div .header-content {
-                            h1 { (config.page_title) }
-                            @if let Some(ref desc) = config.description {
-                                p .description { (desc) }
-                            }
-                        }
-                        div .language-selector {
-                            label for="language-selector" data-i18n="language" { "Language" }
-                            select #language-selector {
-                                option value="en" { "English" }
-                                option value="zh" { "中文" }
-                            }
-                        }
+                (form)
+                (script)
+            }
+        }
+    };
+
+    page.into_string()
+}
+
+/// Generates the description and Open Graph meta tags for the page head
+/// (none when there's no `meta_description`)
+fn generate_meta_tags(config: &WasmFunctionConfig) -> Markup {
+    let description = config.meta_description.as_deref().map(str::trim).filter(|d| !d.is_empty());
+
+    html! {
+        @if let Some(description) = description {
+            meta name="description" content=(description);
+            meta property="og:title" content=(config.page_title);
+            meta property="og:description" content=(description);
+        }
+    }
+}
+
+/// Generates the visible page content: header, form, examples and output
+/// (everything but the header with `bare`)
+fn generate_page_content(config: &WasmFunctionConfig) -> Markup {
+    let form_fields = generate_form_fields(&config.fields, None, config);
+    let subcommand_sections = generate_subcommand_sections(&config.subcommands, config);
+    let examples_section = generate_examples_section(&config.examples);
+
+    // The form, args breakdown, examples and output, without the page chrome
+    let content = html! {
+        @if let Some(ref usage) = config.usage {
+            // The selected subcommand's usage replaces the command's (cli-ui.js)
+            div #usage.usage {
+                span.usage-label data-i18n="usage" { "Usage" }
+                code.usage-line { (usage) }
+                @for subcmd in &config.subcommands {
+                    @if let Some(ref usage) = subcmd.usage {
+                        code.usage-line data-subcommand=(&subcmd.name) hidden { (usage) }
                     }
+                }
+            }
+        }
+        @if config.breadcrumb {
+            // The selected subcommand is appended by cli-ui.js
+            nav #breadcrumb.breadcrumb {
+                span.breadcrumb-item { (config.command_name.as_deref().unwrap_or(&config.page_title)) }
+            }
+        }
 
-                    form #cliForm {
-                        div .form-section {
-                            (form_fields)
-                        }
+        form #cliForm class=[config.is_inline().then_some("inline-form")] {
+            @if config.pinned_fields {
+                // Filled by cli-ui.js; hidden while nothing is pinned
+                div #pinnedFields.form-section.pinned-fields hidden {
+                    h2 data-i18n="pinnedFields" { "Pinned" }
+                }
+            }
+            div .form-section {
+                (form_fields)
+            }
+
+            (subcommand_sections)
+
+            @if config.extra_args {
+                div .form-section.field-group {
+                    label for="extra-args" data-i18n="extraArgs" { "Extra arguments" }
+                    span .help-text data-i18n="extraArgsHelp" {
+                        "Appended to the command line as-is; use quotes for values with spaces"
+                    }
+                    textarea #extra-args name="extra-args" rows="2" {}
+                }
+            }
 
-                        (subcommand_sections)
+            div .button-group {
+                button #runButton type="button" data-i18n="run" { "Run" }
+                button #clearButton.clear-btn type="button" data-i18n="reset" { "Reset" }
+                @if config.share_button {
+                    button #shareButton.share-btn type="button" data-i18n="share" { "Share" }
+                }
+                @if config.required_summary {
+                    span #requiredSummary.required-summary {
+                        span.required-filled { "0" } "/" span.required-total { "0" } " "
+                        span data-i18n="requiredFieldsFilled" { "required fields filled" }
+                    }
+                }
+            }
+        }
 
-                        div .button-group {
-                            button #runButton type="button" data-i18n="run" { "Run" }
-                            button #clearButton.clear-btn type="button" data-i18n="reset" { "Reset" }
+        @if config.args_breakdown {
+            div .args-breakdown {
+                label data-i18n="argsBreakdown" { "Args breakdown" }
+                table #argsBreakdown {
+                    thead {
+                        tr {
+                            th data-i18n="argFlag" { "Flag" }
+                            th data-i18n="argValue" { "Value" }
+                            th data-i18n="argSource" { "Field" }
                         }
                     }
+                    tbody {}
+                }
+            }
+        }
+
+        (examples_section)
 
-                    div .output-section {
-                        label data-i18n="output" { "Output:" }
-                        pre #output data-i18n="noOutputYet" { "No output yet. Fill in the form and click \"Run\"." }
+        div .output-section {
+            // Labeled with the subcommand that produced the output, if any
+            label { span data-i18n="output" { "Output" } span #output-subcommand {} ":" }
+            @if config.run_history {
+                div .run-history {
+                    select #runHistory {
+                        option value="" data-i18n="previousRuns" { "Previous runs" }
                     }
+                    button #restoreRunButton type="button" data-i18n="restoreForm" disabled { "Restore form" }
+                }
+            }
+            @if config.json_tree {
+                div .json-tree-controls {
+                    button #expandAllButton type="button" data-i18n="expandAll" { "Expand all" }
+                    button #collapseAllButton type="button" data-i18n="collapseAll" { "Collapse all" }
+                }
+            }
+            @let terminal = config.output_mode == OutputMode::Append;
+            @let classes = [
+                terminal.then_some("terminal"),
+                config.output_line_numbers.then_some("line-numbers"),
+            ].into_iter().flatten().collect::<Vec<_>>().join(" ");
+            pre #output class=[(!classes.is_empty()).then_some(classes)] data-i18n="noOutputYet" { "No output yet. Fill in the form and click \"Run\"." }
+            @if terminal {
+                button #clearOutputButton.clear-btn type="button" data-i18n="clearTerminal" { "Clear terminal" }
+            }
+        }
 
-                    div #status {}
+        div #status {}
+    };
+
+    html! {
+        @if config.bare {
+            (content)
+        } @else {
+            div .container dir=[config.direction.dir_attr()] {
+                div .header-row {
+                    div .header-content {
+                        h1 { (config.page_title) }
+                        @if let Some(ref desc) = config.description {
+                            p .description { (desc) }
+                        }
+                    }
+                    div .language-selector {
+                        label for="language-selector" data-i18n="language" { "Language" }
+                        select #language-selector {
+                            option value="en" { "English" }
+                            option value="zh" { "中文" }
+                        }
+                    }
                 }
 
-                (generate_script(&config.function_name, &config.package_name, &fields_json, &subcommands_json))
+                (content)
+            }
+        }
+    }
+}
+
+/// Generates the UI as a `<clap-web-ui>` element whose content is attached to a
+/// shadow root from a template
+fn generate_web_component(styles: Markup, content: Markup, script: Markup, nonce: Option<&str>) -> Markup {
+    // Defines the element and points the UI scripts at its shadow root; it must
+    // run before the (deferred) main module script
+    const COMPONENT_JS: &str = include_str!("web-component.js");
+
+    html! {
+        clap-web-ui {}
+        template #clap-web-ui-template {
+            (styles)
+            (content)
+        }
+        script nonce=[nonce] {
+            (PreEscaped(COMPONENT_JS))
+        }
+        (script)
+    }
+}
+
+/// Substitutes `{{NAME}}` placeholders in an HTML shell template
+///
+/// Substitution is done in a single pass, so placeholder-like text inside the
+/// substituted pieces (e.g. in help text) is left alone. Unknown placeholders
+/// are kept as-is.
+fn fill_html_shell(shell: &str, pieces: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(shell.len());
+    let mut rest = shell;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let piece = after.find("}}").and_then(|end| {
+            pieces.iter()
+                .find(|(name, _)| *name == &after[..end])
+                .map(|(_, value)| (*value, end))
+        });
+        match piece {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
             }
         }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Serializes the whole page configuration to JSON
+///
+/// This is the exact data the generated page receives as `window.CLI_CONFIG`,
+/// which makes it a convenient target for snapshot tests: assert on (or store)
+/// the JSON for your command to catch unintended changes in the extracted schema.
+///
+/// # Arguments
+///
+/// * `config` - The configuration to serialize
+///
+/// # Returns
+///
+/// A String containing the configuration as compact JSON
+///
+/// # Example
+///
+/// ```
+/// use clap::Parser;
+/// use clap_web_code_gen::{build_config_for_parser, config_to_json};
+///
+/// #[derive(Parser)]
+/// struct MyArgs {
+///     /// Your name
+///     #[arg(short, long)]
+///     name: String,
+/// }
+///
+/// let config = build_config_for_parser::<MyArgs>("my_package", "", "process_bind");
+/// let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+/// assert_eq!(json["fields"][0]["long"], "name");
+/// assert_eq!(json["fields"][0]["help"], "Your name");
+/// ```
+pub fn config_to_json(config: &WasmFunctionConfig) -> String {
+    serde_json::to_string(&*config.with_field_overrides()).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Serializes the page configuration as an ES module exporting `CLI_CONFIG`
+///
+/// Write this to the `config_module` location; the page imports it instead of
+/// defining the `window.CLI_CONFIG` global.
+///
+/// # Example
+///
+/// ```
+/// use clap::Parser;
+/// use clap_web_code_gen::{build_config_for_parser, config_to_js_module};
+///
+/// #[derive(Parser)]
+/// struct MyArgs {
+///     #[arg(long)]
+///     name: String,
+/// }
+///
+/// let config = build_config_for_parser::<MyArgs>("my_package", "", "process_bind");
+/// assert!(config_to_js_module(&config).starts_with("export const CLI_CONFIG = {"));
+/// ```
+pub fn config_to_js_module(config: &WasmFunctionConfig) -> String {
+    format!("export const CLI_CONFIG = {};\n", config_to_json(config))
+}
+
+/// Renders just the form fields for a field list, for embedding the form in a
+/// custom page layout
+///
+/// The fragment has the same markup and element ids as the fields of a
+/// generated page, with default rendering options. It isn't interactive on
+/// its own: the page must also load the generated JS (`cli-ui.js` with its
+/// placeholders filled in) and define `CLI_CONFIG` for the same fields, e.g.
+/// from [`config_to_json`] or [`config_to_js_module`].
+///
+/// # Example
+///
+/// ```
+/// use clap::{CommandFactory, Parser};
+/// use clap_web_code_gen::{extract_field_descriptors_from_command, render_fields_html};
+///
+/// #[derive(Parser)]
+/// struct MyArgs {
+///     #[arg(long)]
+///     name: String,
+/// }
+///
+/// let fields = extract_field_descriptors_from_command(&MyArgs::command());
+/// assert!(render_fields_html(&fields).contains(r#"id="name""#));
+/// ```
+pub fn render_fields_html(fields: &[FieldDescriptor]) -> String {
+    let config = WasmFunctionConfig {
+        fields: fields.to_vec(),
+        ..Default::default()
+    };
+    generate_form_fields(fields, None, &config).into_string()
+}
+
+/// Renders just the subcommand selector and the subcommands' fields, for
+/// embedding alongside [`render_fields_html`]
+///
+/// As with [`render_fields_html`], the page must also supply the generated JS
+/// and a `CLI_CONFIG` with the same subcommands.
+pub fn render_subcommand_fields_html(subcommands: &[SubcommandDescriptor]) -> String {
+    let config = WasmFunctionConfig {
+        subcommands: subcommands.to_vec(),
+        ..Default::default()
+    };
+    generate_subcommand_sections(subcommands, &config).into_string()
+}
+
+/// Exports a JSON Schema (draft 2020-12) describing a command's arguments
+///
+/// The schema describes an object keyed by field name, with `type`, `enum`,
+/// `minimum`/`maximum` (from the integer type), `default`, `description` and
+/// `required` derived from the same field descriptors the page uses, so any
+/// JSON Schema validator can check form data. Subcommands go under a
+/// `"subcommand"` property: an object with a single key, the subcommand name,
+/// whose value is that subcommand's arguments.
+///
+/// Integer bounds rely on clap's type names, which are only available in
+/// debug builds; release builds omit them.
+///
+/// # Arguments
+///
+/// * `command` - A Clap Command object
+///
+/// # Returns
+///
+/// The schema as a JSON value
+///
+/// # Example
+///
+/// ```
+/// use clap::{Parser, CommandFactory};
+/// use clap_web_code_gen::export_json_schema;
+///
+/// #[derive(Parser)]
+/// struct MyArgs {
+///     /// Your name
+///     #[arg(short, long)]
+///     name: String,
+/// }
+///
+/// let schema = export_json_schema(&MyArgs::command());
+/// assert_eq!(schema["properties"]["name"]["type"], "string");
+/// assert_eq!(schema["required"][0], "name");
+/// ```
+pub fn export_json_schema(command: &Command) -> serde_json::Value {
+    let mut schema = args_json_schema(command);
+    schema["$schema"] = "https://json-schema.org/draft/2020-12/schema".into();
+    schema["title"] = command.get_name().into();
+    if let Some(about) = command.get_about() {
+        schema["description"] = about.to_string().into();
+    }
+
+    let subcommands: Vec<serde_json::Value> = command
+        .get_subcommands()
+        .filter(|subcmd| subcmd.get_name() != "help")
+        .map(|subcmd| serde_json::json!({
+            "type": "object",
+            "properties": { subcmd.get_name(): args_json_schema(subcmd) },
+            "required": [subcmd.get_name()],
+            "additionalProperties": false,
+        }))
+        .collect();
+    if !subcommands.is_empty() {
+        schema["properties"]["subcommand"] = serde_json::json!({ "oneOf": subcommands });
+        if command.is_subcommand_required_set()
+            && let Some(required) = schema["required"].as_array_mut()
+        {
+            required.push("subcommand".into());
+        }
+    }
+
+    schema
+}
+
+/// Object schema for one command's own arguments
+fn args_json_schema(command: &Command) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+
+    for field in extract_field_descriptors_from_command(command) {
+        let arg = command.get_arguments().find(|a| a.get_id() == field.name.as_str());
+        if field.required {
+            required.push(field.name.clone());
+        }
+        properties.insert(field.name.clone(), field_json_schema(&field, arg));
+    }
+
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn field_json_schema(field: &FieldDescriptor, arg: Option<&Arg>) -> serde_json::Value {
+    let mut schema = match &field.field_type {
+        FieldType::String | FieldType::Path | FieldType::IntRange => serde_json::json!({ "type": "string" }),
+        FieldType::Bool => serde_json::json!({ "type": "boolean" }),
+        FieldType::Integer => match arg.and_then(integer_bounds) {
+            Some((minimum, maximum)) => serde_json::json!({ "type": "integer", "minimum": minimum, "maximum": maximum }),
+            None => serde_json::json!({ "type": "integer" }),
+        },
+        FieldType::Float => serde_json::json!({ "type": "number" }),
+        FieldType::Counter => serde_json::json!({ "type": "integer", "minimum": 0 }),
+        // Hidden possible values aren't exposed here either
+        FieldType::Enum(options) if options.is_empty() => serde_json::json!({ "type": "string" }),
+        FieldType::Enum(options) => serde_json::json!({
+            "type": "string",
+            "enum": options.iter().map(|o| o.value.as_str()).collect::<Vec<_>>(),
+        }),
+        FieldType::Vec => serde_json::json!({ "type": "array", "items": { "type": "string" } }),
     };
 
-    page.into_string()
+    if !field.help.is_empty() {
+        schema["description"] = field.help.clone().into();
+    }
+    if let Some(ref default) = field.default_value {
+        // Typed like the field where the default parses as such
+        schema["default"] = match field.field_type {
+            FieldType::Bool => is_truthy(default).into(),
+            FieldType::Integer | FieldType::Counter => default.parse::<i64>()
+                .map(Into::into)
+                .unwrap_or_else(|_| default.clone().into()),
+            FieldType::Float => default.parse::<f64>()
+                .map(Into::into)
+                .unwrap_or_else(|_| default.clone().into()),
+            _ => default.clone().into(),
+        };
+    }
+
+    schema
+}
+
+/// Range of a fixed-size integer arg (by clap's type name, debug builds only);
+/// 64-bit types are left unbounded since JSON numbers can't hold their limits
+fn integer_bounds(arg: &Arg) -> Option<(i64, i64)> {
+    let type_name = format!("{:?}", arg.get_value_parser().type_id());
+    match type_name.as_str() {
+        "u8" => Some((0, u8::MAX.into())),
+        "u16" => Some((0, u16::MAX.into())),
+        "u32" => Some((0, u32::MAX.into())),
+        "i8" => Some((i8::MIN.into(), i8::MAX.into())),
+        "i16" => Some((i16::MIN.into(), i16::MAX.into())),
+        "i32" => Some((i32::MIN.into(), i32::MAX.into())),
+        _ => None,
+    }
 }
 
 /// Simplified UI generation for Parser types
@@ -656,9 +3013,9 @@ pub fn generate_wasm_function_page(config: &WasmFunctionConfig) -> String {
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
 /// use clap::Parser;
-/// use code_gen::generate_ui_for_parser;
+/// use clap_web_code_gen::generate_ui_for_parser;
 ///
 /// #[derive(Parser)]
 /// struct MyArgs {
@@ -697,9 +3054,9 @@ pub fn generate_ui_for_parser<T: clap::Parser + clap::CommandFactory>(
 ///
 /// # Example
 ///
-/// ```
+/// ```no_run
 /// use clap::Parser;
-/// use code_gen::generate_ui_for_parser_with_function;
+/// use clap_web_code_gen::generate_ui_for_parser_with_function;
 ///
 /// #[derive(Parser)]
 /// struct MyArgs {
@@ -720,42 +3077,328 @@ pub fn generate_ui_for_parser_with_function<T: clap::Parser + clap::CommandFacto
     page_title: &str,
     function_name: &str,
 ) -> String {
-    let cmd = T::command();
-    let fields = extract_field_descriptors_from_command(&cmd);
-    let subcommands = extract_subcommands_from_command(&cmd);
-
-    // Extract about and long_about from the command
-    // Use about for the page title (when page_title parameter is empty)
-    // Use long_about for the description
-    let extracted_title = cmd.get_about()
-        .map(|a| a.to_string())
-        .unwrap_or_else(|| cmd.get_name().to_string());
-
-    let extracted_description = cmd.get_long_about()
-        .map(|la| la.to_string());
-
-    let final_title = if page_title.is_empty() {
-        extracted_title
-    } else {
-        page_title.to_string()
-    };
-
-    let config = WasmFunctionConfig {
-        function_name: function_name.to_string(),
-        package_name: package_name.to_string(),
-        page_title: final_title,
-        description: extracted_description,
-        fields,
-        subcommands,
-    };
-
+    let config = build_config_for_parser::<T>(package_name, page_title, function_name);
     generate_wasm_function_page(&config)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
+/// Builds the page configuration for a Parser type without rendering it
+///
+/// This is what `generate_ui_for_parser_with_function` uses internally. Call it
+/// directly when you want to adjust the configuration (e.g., enable optional
+/// UI features) before passing it to `generate_wasm_function_page`.
+///
+/// # Arguments
+///
+/// * `package_name` - The package name (used in import path)
+/// * `page_title` - The title to display on the web page (empty to use the command's about text)
+/// * `function_name` - The name of the WASM-bound function (e.g., "process_bind" for `fn process`)
+///
+/// # Returns
+///
+/// A WasmFunctionConfig with fields, subcommands and examples extracted from the command
+///
+/// # Example
+///
+/// ```no_run
+/// use clap::Parser;
+/// use clap_web_code_gen::{build_config_for_parser, generate_wasm_function_page};
+///
+/// #[derive(Parser)]
+/// struct MyArgs {
+///     #[arg(short, long)]
+///     name: String,
+/// }
+///
+/// let mut config = build_config_for_parser::<MyArgs>("my_package", "", "process_bind");
+/// config.collapse_optional_fields = true;
+/// std::fs::write("ui.html", generate_wasm_function_page(&config)).unwrap();
+/// ```
+pub fn build_config_for_parser<T: clap::CommandFactory>(
+    package_name: &str,
+    page_title: &str,
+    function_name: &str,
+) -> WasmFunctionConfig {
+    build_config_for_command(&T::command(), package_name, page_title, function_name)
+}
+
+/// Builds the page configuration for a `Command` built at runtime
+///
+/// Use this when the CLI isn't a derived `Parser`, e.g. a `Command` assembled
+/// with the builder API from a YAML or JSON definition. Restricted values
+/// (`PossibleValuesParser` or `value_parser([...])`) become dropdowns just like
+/// `ValueEnum` types. The arguments are the same as for
+/// `build_config_for_parser`.
+///
+/// # Example
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use clap_web_code_gen::{build_config_for_command, generate_wasm_function_page};
+///
+/// let cmd = Command::new("my-tool")
+///     .arg(Arg::new("level").long("level").value_parser(["low", "high"]));
+///
+/// let config = build_config_for_command(&cmd, "my_package", "", "process_bind");
+/// assert!(generate_wasm_function_page(&config).contains(r#"<option value="high">"#));
+/// ```
+pub fn build_config_for_command(
+    cmd: &Command,
+    package_name: &str,
+    page_title: &str,
+    function_name: &str,
+) -> WasmFunctionConfig {
+    let fields = extract_field_descriptors_from_command(cmd);
+    let subcommands = extract_subcommands_from_command(cmd);
+    let examples = extract_examples_from_command(cmd);
+
+    // Extract about and long_about from the command
+    // Use about for the page title (when page_title parameter is empty)
+    // Use long_about for the description
+    let extracted_title = cmd.get_about()
+        .map(|a| a.to_string())
+        .unwrap_or_else(|| cmd.get_name().to_string());
+
+    let extracted_description = cmd.get_long_about()
+        .map(|la| la.to_string());
+
+    let final_title = if page_title.is_empty() {
+        extracted_title
+    } else {
+        page_title.to_string()
+    };
+
+    WasmFunctionConfig {
+        function_name: function_name.to_string(),
+        package_name: package_name.to_string(),
+        page_title: final_title,
+        description: extracted_description,
+        meta_description: cmd.get_about().map(|a| a.to_string()),
+        fields,
+        subcommands,
+        examples,
+        allow_external_subcommands: cmd.is_allow_external_subcommands_set(),
+        subcommand_required: cmd.is_subcommand_required_set(),
+        usage: Some(render_usage_text(&mut cmd.clone())),
+        command_name: Some(program_name(cmd)),
+        ..Default::default()
+    }
+}
+
+/// A problem found by `validate_html`; `offset` is the byte offset in the
+/// HTML where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HtmlError {
+    /// A closing tag with no open element to close
+    UnexpectedClosingTag { tag: String, offset: usize },
+    /// An element that is never closed (or is closed by an outer element's
+    /// closing tag)
+    UnclosedTag { tag: String, offset: usize },
+    /// A tag, comment or quoted attribute value that runs to the end of the HTML
+    Unterminated { offset: usize },
+    /// Malformed attribute syntax, e.g. `a=` without a value or a stray quote
+    InvalidAttribute { tag: String, offset: usize },
+}
+
+impl fmt::Display for HtmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HtmlError::UnexpectedClosingTag { tag, offset } => {
+                write!(f, "unexpected closing tag </{}> at byte {}", tag, offset)
+            }
+            HtmlError::UnclosedTag { tag, offset } => write!(f, "<{}> at byte {} is never closed", tag, offset),
+            HtmlError::Unterminated { offset } => write!(f, "markup at byte {} is never terminated", offset),
+            HtmlError::InvalidAttribute { tag, offset } => {
+                write!(f, "invalid attribute in <{}> at byte {}", tag, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HtmlError {}
+
+/// Elements that never have a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+/// Elements whose content is raw text, ending only at their closing tag
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Checks that HTML is well-formed: every tag is terminated and its
+/// attributes are well-formed, and every element is closed by its own closing
+/// tag (void elements excepted)
+///
+/// This is a lightweight guard for generated pages (e.g. in a test after
+/// customizing a shell or template), not a full HTML validator: it doesn't
+/// know which elements may contain which, and it doesn't apply HTML's
+/// optional end tags.
+///
+/// # Example
+///
+/// ```
+/// use clap_web_code_gen::{validate_html, HtmlError};
+///
+/// assert!(validate_html(r#"<p class="note">Hello<br></p>"#).is_ok());
+/// assert_eq!(
+///     validate_html("<div><span></div>"),
+///     Err(vec![HtmlError::UnclosedTag { tag: "span".to_string(), offset: 5 }]),
+/// );
+/// ```
+pub fn validate_html(html: &str) -> Result<(), Vec<HtmlError>> {
+    let bytes = html.as_bytes();
+    let mut errors = Vec::new();
+    // Open elements: (lowercase name, offset of the opening tag)
+    let mut open: Vec<(String, usize)> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = html[pos..].find('<') {
+        let start = pos + found;
+        let rest = &html[start..];
+
+        if rest.starts_with("<!--") {
+            match rest.find("-->") {
+                Some(end) => pos = start + end + 3,
+                None => {
+                    errors.push(HtmlError::Unterminated { offset: start });
+                    break;
+                }
+            }
+            continue;
+        }
+        if rest.starts_with("<!") {
+            // Doctype
+            match rest.find('>') {
+                Some(end) => pos = start + end + 1,
+                None => {
+                    errors.push(HtmlError::Unterminated { offset: start });
+                    break;
+                }
+            }
+            continue;
+        }
+
+        let closing = rest.starts_with("</");
+        let name_start = start + if closing { 2 } else { 1 };
+        let name_len = html[name_start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(html.len() - name_start);
+        if name_len == 0 || !bytes[name_start].is_ascii_alphabetic() {
+            // A lone `<` in text
+            pos = start + 1;
+            continue;
+        }
+        let name = html[name_start..name_start + name_len].to_ascii_lowercase();
+
+        // Attributes, up to the end of the tag
+        let mut i = name_start + name_len;
+        let mut self_closing = false;
+        let mut terminated = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'>' => {
+                    terminated = true;
+                    i += 1;
+                    break;
+                }
+                c if c.is_ascii_whitespace() => i += 1,
+                b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                    self_closing = true;
+                    i += 1;
+                }
+                b'"' | b'\'' | b'=' | b'<' | b'/' => {
+                    errors.push(HtmlError::InvalidAttribute { tag: name.clone(), offset: i });
+                    i += 1;
+                }
+                _ => {
+                    // Attribute name, then an optional value
+                    while i < bytes.len() && !matches!(bytes[i], b'>' | b'/' | b'=' | b'"' | b'\'' | b'<')
+                        && !bytes[i].is_ascii_whitespace()
+                    {
+                        i += 1;
+                    }
+                    if bytes.get(i) != Some(&b'=') {
+                        continue;
+                    }
+                    i += 1;
+                    match bytes.get(i) {
+                        Some(&quote @ (b'"' | b'\'')) => match html[i + 1..].find(quote as char) {
+                            Some(end) => i += end + 2,
+                            None => {
+                                errors.push(HtmlError::Unterminated { offset: i });
+                                return Err(errors);
+                            }
+                        },
+                        Some(c) if !c.is_ascii_whitespace() && !matches!(c, b'>' | b'<' | b'=' | b'`') => {
+                            while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                                if matches!(bytes[i], b'"' | b'\'' | b'<' | b'=' | b'`') {
+                                    errors.push(HtmlError::InvalidAttribute { tag: name.clone(), offset: i });
+                                }
+                                i += 1;
+                            }
+                        }
+                        _ => errors.push(HtmlError::InvalidAttribute { tag: name.clone(), offset: i }),
+                    }
+                }
+            }
+        }
+        if !terminated {
+            errors.push(HtmlError::Unterminated { offset: start });
+            break;
+        }
+        pos = i;
+
+        if closing {
+            match open.iter().rposition(|(tag, _)| *tag == name) {
+                Some(index) => {
+                    // Anything opened inside it and not closed yet is unclosed
+                    for (tag, offset) in open.drain(index..).skip(1) {
+                        errors.push(HtmlError::UnclosedTag { tag, offset });
+                    }
+                }
+                None => errors.push(HtmlError::UnexpectedClosingTag { tag: name, offset: start }),
+            }
+        } else if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            // Skip to the closing tag, which the next iteration handles
+            let end_tag = format!("</{}", name);
+            match html[pos..].to_ascii_lowercase().find(&end_tag) {
+                Some(end) => {
+                    open.push((name, start));
+                    pos += end;
+                }
+                None => {
+                    errors.push(HtmlError::UnclosedTag { tag: name, offset: start });
+                    break;
+                }
+            }
+        } else if !self_closing && !VOID_ELEMENTS.contains(&name.as_str()) {
+            open.push((name, start));
+        }
+    }
+
+    errors.extend(open.into_iter().map(|(tag, offset)| HtmlError::UnclosedTag { tag, offset }));
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config for the "test" function with the given fields, and defaults
+    /// for everything else
+    fn test_config(fields: Vec<FieldDescriptor>) -> WasmFunctionConfig {
+        WasmFunctionConfig {
+            function_name: "test".to_string(),
+            package_name: "test".to_string(),
+            page_title: "Test".to_string(),
+            fields,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_generate_basic_page() {
         let config = WasmFunctionConfig {
@@ -773,17 +3416,19 @@ mod tests {
                     default_value: None,
                     required: false,
                     is_positional: false,
+                    ..Default::default()
                 }
             ],
             subcommands: vec![],
+            ..Default::default()
         };
 
         let html = generate_wasm_function_page(&config);
 
         assert!(html.contains("Test Page"));
         assert!(html.contains("This is a test description"));
-        assert!(html.contains("test_func"));
-        assert!(html.contains("./test_pkg.js"));
+        assert!(html.contains("wasmFunction = wasm.test_func;"));
+        assert!(html.contains("const WASM_IMPORT_PATH = './test_pkg.js';"));
         assert!(html.contains("test_field"));
     }
 
@@ -804,6 +3449,7 @@ mod tests {
                     default_value: Some("default".to_string()),
                     required: true,
                     is_positional: false,
+                    ..Default::default()
                 },
                 FieldDescriptor {
                     name: "enabled".to_string(),
@@ -814,9 +3460,11 @@ mod tests {
                     default_value: None,
                     required: false,
                     is_positional: false,
+                    ..Default::default()
                 },
             ],
             subcommands: vec![],
+            ..Default::default()
         };
 
         let html = generate_wasm_function_page(&config);
@@ -841,16 +3489,18 @@ mod tests {
                     long: Some("color".to_string()),
                     help: "Select color".to_string(),
                     field_type: FieldType::Enum(vec![
-                        EnumOption { value: "red".to_string(), help: "Red color".to_string() },
-                        EnumOption { value: "green".to_string(), help: "Green color".to_string() },
-                        EnumOption { value: "blue".to_string(), help: "Blue color".to_string() },
+                        EnumOption { value: "red".to_string(), help: "Red color".to_string(), icon: None },
+                        EnumOption { value: "green".to_string(), help: "Green color".to_string(), icon: None },
+                        EnumOption { value: "blue".to_string(), help: "Blue color".to_string(), icon: None },
                     ]),
                     default_value: Some("red".to_string()),
                     required: false,
                     is_positional: false,
+                    ..Default::default()
                 },
             ],
             subcommands: vec![],
+            ..Default::default()
         };
 
         let html = generate_wasm_function_page(&config);
@@ -936,5 +3586,2956 @@ mod tests {
         let _tags_field = fields.iter().find(|f| f.name == "tags").unwrap();
 
     }
-}
 
+    #[test]
+    fn test_extract_examples_from_after_help() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test", after_help = "Examples:\n  $ test --name foo\n  $ test -n 'bar baz'\n  not an example\n\nMore text\n  $ test --ignored")]
+        struct TestArgs {
+            /// Name field
+            #[arg(short, long)]
+            name: String,
+        }
+
+        let cmd = TestArgs::command();
+        let examples = extract_examples_from_command(&cmd);
+        assert_eq!(examples, vec!["test --name foo", "test -n 'bar baz'"]);
+
+        let config = WasmFunctionConfig {
+            examples,
+            ..test_config(extract_field_descriptors_from_command(&cmd))
+        };
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"data-command="test --name foo""#));
+        assert!(html.contains("example-chip"));
+    }
+
+    #[test]
+    fn test_collapse_optional_fields() {
+        let config = WasmFunctionConfig {
+            collapse_optional_fields: true,
+            ..test_config(vec![
+                FieldDescriptor {
+                    name: "input".to_string(),
+                    short: Some('i'),
+                    long: Some("input".to_string()),
+                    help: "Input".to_string(),
+                    field_type: FieldType::String,
+                    default_value: None,
+                    required: true,
+                    is_positional: false,
+                    ..Default::default()
+                },
+                FieldDescriptor {
+                    name: "level".to_string(),
+                    short: Some('l'),
+                    long: Some("level".to_string()),
+                    help: "Level".to_string(),
+                    field_type: FieldType::Integer,
+                    default_value: Some("3".to_string()),
+                    required: false,
+                    is_positional: false,
+                    ..Default::default()
+                },
+            ])
+        };
+
+        let html = generate_wasm_function_page(&config);
+        let details_start = html.find("<details class=\"advanced-options\">").unwrap();
+        // Required field is shown up front, optional one is collapsed but keeps its default
+        assert!(html.find("id=\"input\"").unwrap() < details_start);
+        assert!(html.find("id=\"level\"").unwrap() > details_start);
+        assert!(html.contains("value=\"3\""));
+
+        let html = generate_wasm_function_page(&WasmFunctionConfig {
+            collapse_optional_fields: false,
+            ..config
+        });
+        assert!(!html.contains("advanced-options\""));
+    }
+
+    #[test]
+    fn test_config_to_json_snapshot() {
+        use clap::{Parser, Subcommand, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Verbose output
+            #[arg(short, long)]
+            verbose: bool,
+
+            #[command(subcommand)]
+            command: TestCommands,
+        }
+
+        #[derive(Subcommand)]
+        enum TestCommands {
+            /// Add an item
+            Add {
+                /// Item name
+                item: String,
+            },
+        }
+
+        let cmd = TestArgs::command();
+        let config = WasmFunctionConfig {
+            function_name: "run_bind".to_string(),
+            package_name: "test".to_string(),
+            page_title: "Test".to_string(),
+            fields: extract_field_descriptors_from_command(&cmd),
+            subcommands: extract_subcommands_from_command(&cmd),
+            ..Default::default()
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        assert_eq!(json["function_name"], "run_bind");
+        assert_eq!(json["fields"], serde_json::json!([{
+            "name": "verbose",
+            "short": "v",
+            "long": "verbose",
+            "help": "Verbose output",
+            "field_type": { "type": "Bool" },
+            "default_value": null,
+            "required": false,
+            "is_positional": false,
+            "index": null,
+            "ignore_case": false,
+            "hide_possible_values": false,
+            "conditional_defaults": [],
+            "bool_values": null,
+            "value_hint": null,
+            "require_equals": false,
+            "last": false,
+            "help_heading": null,
+            "min_values": null,
+            "value_terminator": null,
+            "allow_hyphen_values": false,
+            "is_uuid": false,
+            "non_utf8_default": false,
+            "display_order": 0,
+            "hide_short_help": false,
+        }]));
+        assert_eq!(json["subcommands"][0]["name"], "add");
+        assert_eq!(json["subcommands"][0]["fields"][0]["is_positional"], true);
+        assert_eq!(json["subcommands"][0]["fields"][0]["index"], 1);
+        assert_eq!(json["subcommands"][0]["fields"][0]["required"], true);
+
+        // The page embeds the same JSON as window.CLI_CONFIG
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(&format!("window.CLI_CONFIG = {};", config_to_json(&config))));
+    }
+
+    #[test]
+    fn test_nested_vec_field() {
+        // Derive can't express Vec<Vec<String>> yet, so build the arg directly
+        let cmd = Command::new("test").arg(
+            Arg::new("group")
+                .long("group")
+                .help("Value groups")
+                .action(ArgAction::Append)
+                .num_args(1..)
+                .value_delimiter(','),
+        );
+        let mut config = test_config(extract_field_descriptors_from_command(&cmd));
+        assert!(matches!(config.fields[0].field_type, FieldType::Vec));
+
+        // Without explicit config the field is a flat Vec
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("add-group-btn\""));
+
+        config.nested_fields.insert("group".to_string(), NestedVecField { delimiter: Some(",".to_string()) });
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"data-delimiter=",""#));
+        assert!(html.contains(r#"id="group-groups""#));
+        assert!(html.contains(r#""nested_fields":{"group":{"delimiter":","}}"#));
+
+        // The flattened flags (--group a,b --group c) parse back into groups
+        let matches = cmd.try_get_matches_from(["test", "--group", "a,b", "--group", "c"]).unwrap();
+        let groups: Vec<Vec<&String>> = matches
+            .get_occurrences::<String>("group")
+            .unwrap()
+            .map(|values| values.collect())
+            .collect();
+        assert_eq!(groups, vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn test_extra_args_textarea() {
+        let config = test_config(vec![]);
+        assert!(!generate_wasm_function_page(&config).contains("id=\"extra-args\""));
+
+        let config = WasmFunctionConfig { extra_args: true, ..config };
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains("<textarea id=\"extra-args\""));
+        assert!(html.contains("\"extra_args\":true"));
+    }
+
+    #[test]
+    fn test_enum_ignore_case_default() {
+        use clap::{Parser, ValueEnum, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Output format
+            #[arg(long, value_enum, ignore_case = true, default_value = "JSON")]
+            format: Format,
+
+            /// Color mode
+            #[arg(long, value_enum, default_value = "ALWAYS")]
+            color: ColorMode,
+        }
+
+        #[derive(Clone, Copy, ValueEnum)]
+        enum Format {
+            Text,
+            Json,
+        }
+
+        #[derive(Clone, Copy, ValueEnum)]
+        enum ColorMode {
+            Auto,
+            Always,
+        }
+
+        let cmd = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let format = fields.iter().find(|f| f.name == "format").unwrap();
+        let color = fields.iter().find(|f| f.name == "color").unwrap();
+        assert!(format.ignore_case);
+        assert!(!color.ignore_case);
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        // Default in different casing selects the option only when the field ignores case
+        assert!(html.contains(r#"<option value="json" selected>"#));
+        assert!(!html.contains(r#"<option value="always" selected>"#));
+    }
+
+    #[test]
+    fn test_primary_field() {
+        let field = |name: &str| FieldDescriptor {
+            name: name.to_string(),
+            long: Some(name.to_string()),
+            help: format!("{} field", name),
+            field_type: FieldType::String,
+            ..Default::default()
+        };
+        let config = WasmFunctionConfig {
+            primary_field: Some("query".to_string()),
+            ..test_config(vec![field("first"), field("query")])
+        };
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.find("id=\"query\"").unwrap() < html.find("id=\"first\"").unwrap());
+        assert!(html.contains(r#"class="field-group primary-field" data-field-name="query""#));
+        assert!(html.contains(r#"class="field-group" data-field-name="first""#));
+        assert_eq!(html.matches(" autofocus").count(), 1);
+    }
+
+    #[test]
+    fn test_subcommand_aliases() {
+        use clap::{Parser, Subcommand, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[command(subcommand)]
+            command: TestCommands,
+        }
+
+        #[derive(Subcommand)]
+        enum TestCommands {
+            /// Remove an item
+            #[command(visible_alias = "rm", alias = "del")]
+            Remove {
+                /// Item name
+                item: String,
+            },
+            /// List items
+            List,
+        }
+
+        let cmd = TestArgs::command();
+        let subcommands = extract_subcommands_from_command(&cmd);
+        let remove = subcommands.iter().find(|s| s.name == "remove").unwrap();
+        // Hidden aliases are not shown
+        assert_eq!(remove.aliases, vec!["rm"]);
+        assert!(subcommands.iter().find(|s| s.name == "list").unwrap().aliases.is_empty());
+
+        let config = WasmFunctionConfig {
+            subcommands,
+            ..test_config(vec![])
+        };
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<option value="remove">Remove an item (remove, aliases: rm)</option>"#));
+        assert!(html.contains(r#"<option value="list">List items (list)</option>"#));
+    }
+
+    #[test]
+    fn test_vec_input_has_field_id() {
+        let config = test_config(vec![FieldDescriptor {
+            name: "tags".to_string(),
+            long: Some("tags".to_string()),
+            field_type: FieldType::Vec,
+            ..Default::default()
+        }]);
+        // The JS looks Vec fields up by id when assembling args
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<input class="vec-input" type="text" id="tags""#));
+    }
+
+    #[test]
+    fn test_number_step_config() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Port
+            #[arg(long, default_value = "8000")]
+            port: u16,
+
+            /// Ratio
+            #[arg(long, default_value = "0.5")]
+            ratio: f64,
+
+            /// Retries
+            #[arg(long, default_value = "3")]
+            retries: u32,
+        }
+
+        let cmd = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&cmd);
+        assert!(matches!(fields.iter().find(|f| f.name == "ratio").unwrap().field_type, FieldType::Float));
+
+        let mut config = test_config(fields);
+        config.field_steps.insert("port".to_string(), "100".to_string());
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"id="port" name="port" value="8000" step="100""#));
+        assert!(html.contains(r#"id="ratio" name="ratio" value="0.5" step="any""#));
+        // Integers without a configured step keep the browser default of 1
+        assert!(html.contains(r#"id="retries" name="retries" value="3">"#));
+    }
+
+    #[test]
+    fn test_required_positional_textarea() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Input text
+            input: String,
+
+            /// Output path
+            output: Option<String>,
+        }
+
+        let cmd = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let input = fields.iter().find(|f| f.name == "input").unwrap();
+        assert!(input.required && input.is_positional);
+
+        let config = test_config(fields);
+        // The JS validates required positionals before Run; the textarea must
+        // carry both markers for that
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"data-field-name="input" data-is-positional="true""#));
+        assert!(html.contains(r#"<textarea id="input" name="input" placeholder="Input text" required"#));
+        assert!(html.contains(r#"<textarea id="output" name="output" placeholder="Output path" rows="5""#));
+        assert!(html.contains("positionalGap"));
+    }
+
+    #[test]
+    fn test_field_order_alphabetical() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Zeta
+            #[arg(long)]
+            zeta: Option<String>,
+
+            /// Source
+            source: String,
+
+            /// Alpha
+            #[arg(long)]
+            alpha: Option<String>,
+
+            /// Dest
+            dest: String,
+        }
+
+        let cmd = TestArgs::command();
+        let mut config = test_config(extract_field_descriptors_from_command(&cmd));
+
+        let order = |html: &str| {
+            let mut names: Vec<&str> = ["zeta", "source", "alpha", "dest"].into_iter().collect();
+            names.sort_by_key(|n| html.find(&format!("data-field-name=\"{}\"", n)).unwrap());
+            names
+        };
+
+        assert_eq!(order(&generate_wasm_function_page(&config)), ["zeta", "source", "alpha", "dest"]);
+
+        config.field_order = FieldOrder::Alphabetical;
+        assert_eq!(order(&generate_wasm_function_page(&config)), ["alpha", "source", "zeta", "dest"]);
+    }
+
+    #[test]
+    fn test_label_style_name_with_description() {
+        let mut config = test_config(vec![FieldDescriptor {
+            name: "name".to_string(),
+            short: Some('n'),
+            long: Some("name".to_string()),
+            help: "Your name".to_string(),
+            ..Default::default()
+        }]);
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<label for="name">Your name</label>"#));
+        assert!(!html.contains("field-description\">"));
+
+        config.label_style = LabelStyle::NameWithDescription;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<label for="name">name</label>"#));
+        assert!(html.contains(r#"<span class="help-text"> (-n, --name)</span><span class="field-description">Your name</span>"#));
+    }
+
+    #[test]
+    fn test_auto_run_config() {
+        let mut config = test_config(vec![]);
+        assert!(config_to_json(&config).contains(r#""auto_run":false"#));
+
+        config.auto_run = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#""auto_run":true"#));
+        assert!(html.contains("wasmInit.then(autoRun);"));
+    }
+
+    #[test]
+    fn test_value_less_flags() {
+        use clap::{Arg, ArgAction, Command};
+
+        let cmd = Command::new("test")
+            .arg(Arg::new("fast").long("fast").num_args(0).default_missing_value("yes"))
+            .arg(Arg::new("mark").long("mark").num_args(0).action(ArgAction::Append).default_missing_value("x"))
+            .arg(Arg::new("level").short('l').num_args(0).action(ArgAction::Count))
+            .arg(Arg::new("name").long("name"));
+
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let field_type = |name: &str| &fields.iter().find(|f| f.name == name).unwrap().field_type;
+        assert!(matches!(field_type("fast"), FieldType::Bool));
+        assert!(matches!(field_type("mark"), FieldType::Bool));
+        assert!(matches!(field_type("level"), FieldType::Counter));
+        assert!(matches!(field_type("name"), FieldType::String));
+    }
+
+    #[test]
+    fn test_extract_conditional_defaults() {
+        use clap::{Arg, ArgAction, Command};
+        use clap::builder::ArgPredicate;
+
+        let cmd = Command::new("test")
+            .arg(Arg::new("mode").long("mode"))
+            .arg(Arg::new("fast").long("fast").action(ArgAction::SetTrue))
+            .arg(Arg::new("level").long("level")
+                .default_value_if("mode", "turbo", Some("3"))
+                .default_value_if("fast", ArgPredicate::IsPresent, Some("say \"hi\""))
+                .default_value_if("mode", "off", None));
+
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let level = fields.iter().find(|f| f.name == "level").unwrap();
+        let rules: Vec<_> = level.conditional_defaults.iter()
+            .map(|r| (r.trigger.as_str(), r.value.as_deref(), r.default.as_str()))
+            .collect();
+        assert_eq!(rules, [("mode", Some("turbo"), "3"), ("fast", None, "say \"hi\"")]);
+        assert!(fields.iter().find(|f| f.name == "mode").unwrap().conditional_defaults.is_empty());
+    }
+
+    #[test]
+    fn test_subcommand_categories() {
+        let subcommand = |name: &str| SubcommandDescriptor {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut config = WasmFunctionConfig {
+            subcommands: vec![subcommand("add"), subcommand("help-me"), subcommand("remove"), subcommand("push")],
+            ..test_config(vec![])
+        };
+
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("<optgroup"));
+
+        config.subcommand_categories.insert("add".to_string(), "Files".to_string());
+        config.subcommand_categories.insert("remove".to_string(), "Files".to_string());
+        config.subcommand_categories.insert("push".to_string(), "Remote".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<option value="help-me">help-me</option>"#,
+            r#"<optgroup label="Files"><option value="add">add</option><option value="remove">remove</option></optgroup>"#,
+            r#"<optgroup label="Remote"><option value="push">push</option></optgroup>"#,
+        )));
+    }
+
+    #[test]
+    fn test_boolish_value_parser() {
+        use clap::{ArgAction, Parser, CommandFactory};
+        use clap::builder::BoolishValueParser;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Colorize output
+            #[arg(long, action = ArgAction::Set, value_parser = BoolishValueParser::new(), default_value = "yes")]
+            color: bool,
+
+            /// Verbose output
+            #[arg(long)]
+            verbose: bool,
+        }
+
+        let cmd = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let color = fields.iter().find(|f| f.name == "color").unwrap();
+        let verbose = fields.iter().find(|f| f.name == "verbose").unwrap();
+        assert!(matches!(color.field_type, FieldType::Bool));
+        let literals = color.bool_values.as_ref().unwrap();
+        assert_eq!((literals.true_value.as_str(), literals.false_value.as_str()), ("true", "false"));
+        assert!(verbose.bool_values.is_none());
+
+        // Both literals are accepted by the parser
+        for literal in ["true", "false"] {
+            assert!(TestArgs::try_parse_from(["test", "--color", literal]).is_ok());
+        }
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<input type="checkbox" id="color" name="color" checked>"#));
+        assert!(html.contains(r#"<input type="checkbox" id="verbose" name="verbose">"#));
+    }
+
+    #[test]
+    fn test_custom_html_shell() {
+        let mut config = WasmFunctionConfig {
+            function_name: "test".to_string(),
+            package_name: "test".to_string(),
+            page_title: "Tom & Jerry".to_string(),
+            description: Some("Literal {{SCRIPT}} in help".to_string()),
+            ..Default::default()
+        };
+        let built_in = generate_wasm_function_page(&config);
+
+        config.html_shell = Some(concat!(
+            "<!DOCTYPE html><html><head><title>{{TITLE}}</title>",
+            r#"<meta name="robots" content="noindex">{{STYLES}}</head>"#,
+            "<body>{{FORM}}{{SCRIPT}}{{UNKNOWN}}</body></html>",
+        ).to_string());
+        let html = generate_wasm_function_page(&config);
+
+        assert!(html.starts_with("<!DOCTYPE html><html><head><title>Tom &amp; Jerry</title><meta name=\"robots\" content=\"noindex\"><style>"));
+        assert!(html.contains("<p class=\"description\">Literal {{SCRIPT}} in help</p>"));
+        assert!(html.ends_with("</script>{{UNKNOWN}}</body></html>"));
+        // The pieces are the same as in the built-in shell
+        let form_start = html.find("<div class=\"container\">").unwrap();
+        assert!(built_in.contains(&html[form_start..html.len() - "{{UNKNOWN}}</body></html>".len()]));
+    }
+
+    #[test]
+    fn test_output_label_has_subcommand_slot() {
+        let config = test_config(vec![]);
+        // The script fills the slot with the subcommand that ran
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<label><span data-i18n="output">Output</span><span id="output-subcommand"></span>:</label>"#));
+        assert!(html.contains("setOutputSubcommand(selectedSubcommand === EXTERNAL_SUBCOMMAND"));
+    }
+
+    #[test]
+    fn test_hide_possible_values() {
+        use clap::{Parser, ValueEnum, CommandFactory};
+
+        #[derive(Clone, ValueEnum)]
+        enum Mode {
+            Public,
+            Secret,
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Mode
+            #[arg(long, value_enum, hide_possible_values = true)]
+            mode: Mode,
+
+            /// Level
+            #[arg(long, value_enum)]
+            level: Mode,
+        }
+
+        let cmd = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let mode = fields.iter().find(|f| f.name == "mode").unwrap();
+        assert!(mode.hide_possible_values);
+        assert!(matches!(&mode.field_type, FieldType::Enum(options) if options.is_empty()));
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"<select id="mode""#));
+        assert!(html.contains(r#"<input type="text" id="mode" name="mode" value="" placeholder="Mode" required>"#));
+        assert!(html.contains(r#"<select id="level""#));
+        // Only the unhidden field lists the values (in its select and the JSON config)
+        assert_eq!(html.matches("secret").count(), 2);
+    }
+
+    #[test]
+    fn test_web_component_output() {
+        let config = WasmFunctionConfig {
+            web_component: true,
+            ..test_config(vec![])
+        };
+        let html = generate_wasm_function_page(&config);
+
+        // An embeddable fragment, not a full page
+        assert!(html.starts_with(r#"<clap-web-ui></clap-web-ui><template id="clap-web-ui-template"><style>"#));
+        assert!(!html.contains("<!DOCTYPE html>"));
+        assert!(!html.contains("<body>"));
+        // The form lives in the template; the element is defined before the main script runs
+        let template_end = html.find("</template>").unwrap();
+        assert!(html.find(r#"<form id="cliForm">"#).unwrap() < template_end);
+        let define = html.find("customElements.define('clap-web-ui'").unwrap();
+        assert!(template_end < define && define < html.find(r#"<script type="module">"#).unwrap());
+    }
+
+    #[test]
+    fn test_external_subcommands() {
+        use clap::{Parser, Subcommand};
+        use std::ffi::OsString;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[command(subcommand)]
+            command: TestCommands,
+        }
+
+        #[derive(Subcommand)]
+        #[command(allow_external_subcommands = true)]
+        #[allow(dead_code)]
+        enum TestCommands {
+            /// Build it
+            Build,
+            #[command(external_subcommand)]
+            External(Vec<OsString>),
+        }
+
+        let config = build_config_for_parser::<TestArgs>("test", "", "run_bind");
+        assert!(config.allow_external_subcommands);
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<option value="__external__" data-i18n="externalSubcommand">Other (custom)</option>"#));
+        assert!(html.contains(r#"id="subcommand-__external__""#));
+        assert!(html.contains(r#"<input id="external-subcommand-name" type="text" name="external-subcommand-name" required>"#));
+
+        // Without subcommands or the external option there's no selector
+        let mut config = config;
+        config.allow_external_subcommands = false;
+        config.subcommands.clear();
+        assert!(!generate_wasm_function_page(&config).contains(r#"id="subcommand-selector""#));
+    }
+
+    #[test]
+    fn test_number_units() {
+        let mut config = test_config(vec![
+            FieldDescriptor {
+                name: "timeout".to_string(),
+                long: Some("timeout".to_string()),
+                field_type: FieldType::Integer,
+                default_value: Some("30".to_string()),
+                ..Default::default()
+            },
+            FieldDescriptor {
+                name: "size".to_string(),
+                long: Some("size".to_string()),
+                field_type: FieldType::Float,
+                ..Default::default()
+            },
+        ]);
+        config.field_units.insert("timeout".to_string(), "s".to_string());
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<div class="input-with-unit"><input type="number" id="timeout" name="timeout" value="30"><span class="input-unit">s</span></div>"#));
+        assert!(html.contains(r#"<input type="number" id="size" name="size" value="0" step="any">"#));
+        assert_eq!(html.matches(r#"<span class="input-unit">"#).count(), 1);
+    }
+
+    #[test]
+    fn test_checkbox_set() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Features to enable
+            #[arg(long, default_value = "safe")]
+            feature: Vec<String>,
+        }
+
+        let cmd = TestArgs::command();
+        let mut config = test_config(extract_field_descriptors_from_command(&cmd));
+        config.checkbox_sets.insert("feature".to_string(), vec![
+            CheckboxOption { label: "Fast mode".to_string(), value: "fast".to_string() },
+            CheckboxOption { label: "Safe mode".to_string(), value: "safe".to_string() },
+        ]);
+
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"class="vec-input""#));
+        assert!(html.contains(concat!(
+            r#"<div class="checkbox-set" id="feature">"#,
+            r#"<label class="checkbox-set-option"><input type="checkbox" id="feature-0" value="fast">Fast mode</label>"#,
+            r#"<label class="checkbox-set-option"><input type="checkbox" id="feature-1" value="safe" checked>Safe mode</label>"#,
+            "</div>",
+        )));
+        assert!(html.contains(r#""checkbox_sets":{"feature":[{"label":"Fast mode","value":"fast"}"#));
+    }
+
+    #[test]
+    fn test_subcommand_positional_order() {
+        use clap::{Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Copy a file
+            Copy {
+                /// Destination path
+                #[arg(index = 2)]
+                dest: String,
+                /// Source path
+                #[arg(index = 1)]
+                src: String,
+            },
+        }
+
+        let config = build_config_for_parser::<TestArgs>("test", "", "run_bind");
+        let fields = &config.subcommands[0].fields;
+        let index_of = |name: &str| fields.iter().find(|f| f.name == name).unwrap().index;
+        assert_eq!(index_of("src"), Some(1));
+        assert_eq!(index_of("dest"), Some(2));
+
+        // The JS orders and validates positionals by index within the
+        // subcommand section, so both must be marked required and positional
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<textarea id="copy-src" name="copy-src" placeholder="Source path" required"#));
+        assert!(html.contains(r#"<textarea id="copy-dest" name="copy-dest" placeholder="Destination path" required"#));
+        assert!(html.contains(r#""name":"dest","short":null,"long":null,"help":"Destination path","field_type":{"type":"String"},"default_value":null,"required":true,"is_positional":true,"index":2"#));
+        assert!(html.contains("pushPositionalArgs(subcommandConfig.fields, subcommand, args)"));
+    }
+
+    #[test]
+    fn test_config_module() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains("window.CLI_CONFIG = {"));
+        assert!(html.contains("const CONFIG_MODULE = '';"));
+
+        // The config comes from the module instead of a global
+        config.config_module = Some("./index.config.js".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("window.CLI_CONFIG = {"));
+        assert!(html.contains("const CONFIG_MODULE = './index.config.js';"));
+        assert!(html.contains("return (await import(CONFIG_MODULE)).CLI_CONFIG;"));
+
+        let module = config_to_js_module(&config);
+        assert_eq!(module, format!("export const CLI_CONFIG = {};\n", config_to_json(&config)));
+        assert!(!module.contains("config_module"));
+    }
+
+    #[test]
+    fn test_inline_field_errors() {
+        let config = test_config(vec![]);
+
+        // Validation messages go beneath each field group, not the status line
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(".field-error {"));
+        assert!(html.contains("fieldError.className = 'field-error';"));
+        assert!(!html.contains("fixValidationErrors"));
+
+        // The first invalid field is scrolled to and focused: its group, which
+        // gets the error, holds the control to focus
+        let config = test_config(vec![FieldDescriptor {
+            name: "name".to_string(),
+            long: Some("name".to_string()),
+            required: true,
+            ..Default::default()
+        }]);
+        let html = generate_wasm_function_page(&config);
+        let group = html.find(r#"<div class="field-group" data-field-name="name""#).unwrap();
+        let control = html.find(r#"<input type="text" id="name" name="name" value="" placeholder="name" required></div>"#).unwrap();
+        assert!(group < control);
+        assert!(!html[group..control].contains("</div>"));
+    }
+
+    #[test]
+    fn test_external_config() {
+        let mut config = test_config(vec![]);
+        assert!(generate_wasm_function_page(&config).contains("window.CLI_CONFIG = {"));
+
+        // No inline config script; cli-ui.js fetches the sidecar file instead
+        config.external_config = Some("./index.config.json".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("window.CLI_CONFIG = {"));
+        assert!(html.contains("const CONFIG_PATH = './index.config.json';"));
+        assert!(!config_to_json(&config).contains("external_config"));
+    }
+
+    #[test]
+    fn test_script_nonce() {
+        let mut config = test_config(vec![]);
+        assert!(!generate_wasm_function_page(&config).contains("nonce="));
+
+        config.script_nonce = Some("r4nd0m".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches(r#"<script nonce="r4nd0m">"#).count(), 3);
+        assert!(html.contains(r#"<script type="module" nonce="r4nd0m">"#));
+        assert!(html.contains(r#"<style nonce="r4nd0m">"#));
+        assert!(!config_to_json(&config).contains("r4nd0m"));
+
+        config.web_component = true;
+        let fragment = generate_wasm_function_page(&config);
+        assert_eq!(fragment.matches(r#"<script nonce="r4nd0m">"#).count(), 4);
+    }
+
+    #[test]
+    fn test_clear_buttons() {
+        use clap::{Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Name
+            #[arg(long)]
+            name: String,
+            /// Verbose
+            #[arg(long)]
+            verbose: bool,
+            #[command(subcommand)]
+            command: Option<Commands>,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Add things
+            Add {
+                /// Tags
+                #[arg(long)]
+                tag: Vec<String>,
+            },
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "", "run_bind");
+        assert!(!generate_wasm_function_page(&config).contains("<button class=\"clear-field-btn\""));
+
+        // Keyed by element id, so subcommand fields resolve to their prefixed input
+        config.clear_buttons = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<button class="clear-field-btn" type="button" data-field-name="name" title="Clear" data-i18n-title="clearField">×</button>"#));
+        assert!(html.contains(r#"<button class="clear-field-btn" type="button" data-field-name="add-tag" title="Clear" data-i18n-title="clearField">×</button>"#));
+        assert!(!html.contains(r#"data-field-name="verbose" title="Clear""#));
+    }
+
+    #[test]
+    fn test_path_fields() {
+        use clap::{Parser, CommandFactory, ValueHint};
+        use std::path::PathBuf;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Output file
+            #[arg(long)]
+            output: PathBuf,
+            /// Working directory
+            #[arg(long, value_hint = ValueHint::DirPath)]
+            dir: Option<String>,
+            /// Name
+            #[arg(long)]
+            name: String,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let field_type = |name: &str| &fields.iter().find(|f| f.name == name).unwrap().field_type;
+        assert!(matches!(field_type("output"), FieldType::Path));
+        assert!(matches!(field_type("dir"), FieldType::Path));
+        assert!(matches!(field_type("name"), FieldType::String));
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<input type="text" id="output" name="output" value="" placeholder="path/to/file" data-i18n="pathPlaceholder" required>"#));
+        assert!(html.contains(r#"<input class="path-file-input" type="file" data-field-name="output">"#));
+    }
+
+    #[test]
+    fn test_required_marker_all_field_types() {
+        let field = |name: &str, field_type: FieldType| FieldDescriptor {
+            name: name.to_string(),
+            long: Some(name.to_string()),
+            help: format!("Help for {}", name),
+            field_type,
+            required: true,
+            ..Default::default()
+        };
+        let option = EnumOption { value: "a".to_string(), help: String::new(), icon: None };
+        let mut config = test_config(vec![
+            field("string", FieldType::String),
+            field("bool", FieldType::Bool),
+            field("integer", FieldType::Integer),
+            field("float", FieldType::Float),
+            field("counter", FieldType::Counter),
+            field("enum", FieldType::Enum(vec![option])),
+            field("vec", FieldType::Vec),
+            field("path", FieldType::Path),
+            field("nested", FieldType::Vec),
+            field("set", FieldType::Vec),
+        ]);
+        config.nested_fields.insert("nested".to_string(), NestedVecField { delimiter: None });
+        config.checkbox_sets.insert("set".to_string(), vec![
+            CheckboxOption { label: "A".to_string(), value: "a".to_string() },
+        ]);
+
+        let html = generate_wasm_function_page(&config);
+        let marker = r#" <span class="required-marker" aria-hidden="true">*</span><span class="sr-only" data-i18n="requiredField">Required field</span>"#;
+        for f in &config.fields {
+            assert!(html.contains(&format!("Help for {}{}", f.name, marker)), "no marker for {}", f.name);
+        }
+        assert_eq!(html.matches(marker).count(), config.fields.len());
+    }
+
+    #[test]
+    fn test_positionals_render_in_index_order() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Third
+            #[arg(index = 3)]
+            third: Option<String>,
+            /// Verbose
+            #[arg(long)]
+            verbose: bool,
+            /// First
+            #[arg(index = 1)]
+            first: String,
+            /// Quiet
+            #[arg(long)]
+            quiet: bool,
+            /// Second
+            #[arg(index = 2)]
+            second: String,
+        }
+
+        let config = build_config_for_parser::<TestArgs>("test", "", "run_bind");
+        let html = generate_wasm_function_page(&config);
+        let position = |name: &str| html.find(&format!(r#"data-field-name="{}""#, name)).unwrap();
+
+        // Positionals fill the positional slots by index; flags stay put
+        assert!(position("first") < position("verbose"));
+        assert!(position("verbose") < position("second"));
+        assert!(position("second") < position("quiet"));
+        assert!(position("quiet") < position("third"));
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let field = |name: &str, field_type: FieldType| FieldDescriptor {
+            name: name.to_string(),
+            long: Some(name.to_string()),
+            field_type,
+            ..Default::default()
+        };
+        let subcommand = |name: &str, fields: Vec<FieldDescriptor>| SubcommandDescriptor {
+            name: name.to_string(),
+            fields,
+            ..Default::default()
+        };
+        let valid = || WasmFunctionConfig {
+            function_name: "run_bind".to_string(),
+            package_name: "test".to_string(),
+            fields: vec![field("name", FieldType::String)],
+            subcommands: vec![subcommand("add", vec![field("name", FieldType::String)])],
+            ..Default::default()
+        };
+        assert_eq!(valid().validate(), Ok(()));
+
+        // Empty identifiers; all problems are reported
+        let mut config = valid();
+        config.function_name.clear();
+        config.package_name.clear();
+        config.fields.push(field("", FieldType::String));
+        config.subcommands.push(subcommand("", vec![]));
+        config.subcommands[0].fields.push(field("", FieldType::String));
+        assert_eq!(config.validate(), Err(vec![
+            ConfigError::EmptyFunctionName,
+            ConfigError::EmptyPackageName,
+            ConfigError::EmptySubcommandName,
+            ConfigError::EmptyFieldName { subcommand: None },
+            ConfigError::EmptyFieldName { subcommand: Some("add".to_string()) },
+        ]));
+
+        // Duplicate names
+        let mut config = valid();
+        config.fields.push(field("name", FieldType::Integer));
+        config.subcommands.push(subcommand("add", vec![]));
+        assert_eq!(config.validate(), Err(vec![
+            ConfigError::DuplicateSubcommand { name: "add".to_string() },
+            ConfigError::DuplicateFieldName { subcommand: None, name: "name".to_string() },
+        ]));
+
+        // Ids colliding across sections and with the page's own elements
+        let mut config = valid();
+        config.fields.push(field("add-name", FieldType::String));
+        config.fields.push(field("output", FieldType::String));
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec![
+            ConfigError::ConflictingId { id: "output".to_string() },
+            ConfigError::ConflictingId { id: "add-name".to_string() },
+        ]);
+        assert_eq!(errors[1].to_string(), "HTML id 'add-name' is used more than once");
+
+        // Enums need options unless they're hidden on purpose
+        let mut config = valid();
+        config.subcommands[0].fields.push(field("mode", FieldType::Enum(vec![])));
+        config.fields.push(FieldDescriptor {
+            hide_possible_values: true,
+            ..field("level", FieldType::Enum(vec![]))
+        });
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec![
+            ConfigError::EmptyEnum { subcommand: Some("add".to_string()), name: "mode".to_string() },
+        ]);
+        assert_eq!(errors[0].to_string(), "field 'mode' of subcommand 'add' has no options");
+    }
+
+    #[test]
+    fn test_flag_preference() {
+        let mut config = test_config(vec![]);
+        assert_eq!(config.flag_preference, FlagPreference::ShortWhenAvailable);
+        assert!(config_to_json(&config).contains(r#""flag_preference":"ShortWhenAvailable""#));
+
+        config.flag_preference = FlagPreference::AlwaysLong;
+        assert!(config_to_json(&config).contains(r#""flag_preference":"AlwaysLong""#));
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#""flag_preference":"AlwaysLong""#));
+    }
+
+    #[test]
+    fn test_empty_string_default() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Prefix
+            #[arg(long, default_value = "")]
+            prefix: String,
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+            /// Mode
+            #[arg(long, value_parser = ["", "fast"], default_value = "")]
+            mode: String,
+            /// Other mode
+            #[arg(long, value_parser = ["", "fast"])]
+            other: Option<String>,
+        }
+
+        // An explicit empty default stays distinct from no default
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let default_of = |name: &str| fields.iter().find(|f| f.name == name).unwrap().default_value.clone();
+        assert_eq!(default_of("prefix"), Some(String::new()));
+        assert_eq!(default_of("name"), None);
+        assert_eq!(default_of("mode"), Some(String::new()));
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#""name":"prefix","short":null,"long":"prefix","help":"Prefix","field_type":{"type":"String"},"default_value":"""#));
+
+        // The empty option is the default, so no placeholder is needed
+        assert!(html.contains(r#"<select id="mode" name="mode"><option value="" selected></option>"#));
+        // Without a default the placeholder is selected, not the empty option
+        assert!(html.contains(r#"<select id="other" name="other"><option value="" selected data-i18n="selectOption">-- Select an option --</option><option value=""></option>"#));
+    }
+
+    #[test]
+    fn test_export_json_schema() {
+        use clap::{Parser, Subcommand, ValueEnum, CommandFactory};
+
+        #[derive(Clone, ValueEnum)]
+        enum Format { Json, Yaml }
+
+        #[derive(Parser)]
+        #[command(name = "test", about = "Test tool")]
+        struct TestArgs {
+            /// Output format
+            #[arg(long, value_enum, default_value = "json")]
+            format: Format,
+            /// Port
+            #[arg(long, default_value = "8080")]
+            port: u16,
+            /// Verbosity
+            #[arg(short, action = clap::ArgAction::Count)]
+            verbose: u8,
+            /// Input file
+            input: String,
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Add tags
+            Add {
+                /// Tags
+                #[arg(long)]
+                tag: Vec<String>,
+                /// Dry run
+                #[arg(long)]
+                dry_run: bool,
+            },
+        }
+
+        let schema = export_json_schema(&TestArgs::command());
+        assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema");
+        assert_eq!(schema["title"], "test");
+        assert_eq!(schema["description"], "Test tool");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], serde_json::json!(["input", "subcommand"]));
+
+        let properties = &schema["properties"];
+        assert_eq!(properties["format"], serde_json::json!({
+            "type": "string",
+            "enum": ["json", "yaml"],
+            "description": "Output format",
+            "default": "json",
+        }));
+        assert_eq!(properties["port"]["type"], "integer");
+        assert_eq!(properties["port"]["default"], 8080);
+        if cfg!(debug_assertions) {
+            assert_eq!(properties["port"]["minimum"], 0);
+            assert_eq!(properties["port"]["maximum"], 65535);
+        }
+        assert_eq!(properties["verbose"]["minimum"], 0);
+        assert_eq!(properties["input"]["type"], "string");
+
+        let add = &properties["subcommand"]["oneOf"][0];
+        assert_eq!(add["required"], serde_json::json!(["add"]));
+        let add_properties = &add["properties"]["add"]["properties"];
+        assert_eq!(add_properties["tag"], serde_json::json!({
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Tags",
+        }));
+        assert_eq!(add_properties["dry_run"]["type"], "boolean");
+    }
+
+    #[test]
+    fn test_help_and_version_actions_skipped() {
+        use clap::{ArgAction, CommandFactory, Parser};
+
+        #[derive(Parser)]
+        #[command(name = "test", version, disable_help_flag = true, disable_version_flag = true)]
+        struct TestArgs {
+            /// Show usage
+            #[arg(short = '?', long = "usage", action = ArgAction::Help)]
+            usage: Option<bool>,
+            /// Show the release
+            #[arg(long = "release", action = ArgAction::Version)]
+            release: Option<bool>,
+            /// A regular flag that happens to be called version
+            #[arg(long)]
+            version: Option<String>,
+            /// Name
+            #[arg(long)]
+            name: String,
+        }
+
+        let cmd = TestArgs::command();
+        let names: Vec<String> = extract_field_descriptors_from_command(&cmd)
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, ["version", "name"]);
+
+        // The args clap adds itself are skipped too once the command is built
+        let mut built = clap::Command::new("test").version("1.0").arg(clap::Arg::new("name").long("name"));
+        built.build();
+        let names: Vec<String> = extract_field_descriptors_from_command(&built)
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, ["name"]);
+    }
+
+    #[test]
+    fn test_flattened_args_and_subcommands() {
+        use clap::{Args, Parser, Subcommand};
+
+        #[derive(Args)]
+        struct CommonArgs {
+            /// Verbose output
+            #[arg(long)]
+            verbose: bool,
+            #[command(flatten)]
+            output: OutputArgs,
+        }
+
+        #[derive(Args)]
+        struct OutputArgs {
+            /// Output path
+            #[arg(long)]
+            out: Option<String>,
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[command(flatten)]
+            common: CommonArgs,
+            /// Name
+            #[arg(long)]
+            name: String,
+            #[command(subcommand)]
+            command: Option<Commands>,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Add things
+            Add {
+                #[command(flatten)]
+                common: OutputArgs,
+            },
+            #[command(flatten)]
+            Extra(ExtraCommands),
+        }
+
+        #[derive(Subcommand)]
+        enum ExtraCommands {
+            /// Remove things
+            Remove,
+        }
+
+        // Flattened (and nested flattened) args are plain top-level fields, once each
+        let config = build_config_for_parser::<TestArgs>("test", "", "run_bind");
+        let names: Vec<&str> = config.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["verbose", "out", "name"]);
+
+        // Flattened subcommand enums contribute their variants as siblings
+        let subcommands: Vec<&str> = config.subcommands.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(subcommands, ["add", "remove"]);
+        assert_eq!(config.subcommands[0].fields.len(), 1);
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_output_mode_append() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<pre id="output" data-i18n="noOutputYet">"#));
+        assert!(!html.contains(r#"id="clearOutputButton""#));
+        assert!(html.contains(r#""output_mode":"Replace""#));
+
+        config.output_mode = OutputMode::Append;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<pre id="output" class="terminal" data-i18n="noOutputYet">"#));
+        assert!(html.contains(r#"<button class="clear-btn" id="clearOutputButton" type="button" data-i18n="clearTerminal">Clear terminal</button>"#));
+        assert!(html.contains(r#""output_mode":"Append""#));
+    }
+
+    #[test]
+    fn test_value_hint_inputs() {
+        use clap::{Parser, CommandFactory, ValueHint};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Homepage
+            #[arg(long, value_hint = ValueHint::Url)]
+            homepage: String,
+            /// Contact
+            #[arg(long, value_hint = ValueHint::EmailAddress)]
+            email: Option<String>,
+            /// Remote host
+            #[arg(value_hint = ValueHint::Hostname)]
+            host: String,
+            /// Name
+            #[arg(long)]
+            name: String,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let hint = |name: &str| fields.iter().find(|f| f.name == name).unwrap().value_hint.clone();
+        assert_eq!(hint("homepage").as_deref(), Some("Url"));
+        assert_eq!(hint("name"), None);
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<input type="url" id="homepage" name="homepage" value="" placeholder="https://example.com" required>"#));
+        assert!(html.contains(r#"<input type="email" id="email" name="email" value="" placeholder="user@example.com">"#));
+        // A hinted positional gets an input rather than a textarea
+        assert!(html.contains(r#"<input type="text" id="host" name="host" value="" placeholder="example.com" required"#));
+        assert!(html.contains(r#"<input type="text" id="name" name="name" value="" placeholder="Name" required>"#));
+    }
+
+    #[test]
+    fn test_external_css() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(".field-error {"));
+        assert!(!html.contains("<link"));
+
+        config.external_css = Some("./cli-ui.css".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<link rel="stylesheet" href="./cli-ui.css">"#));
+        assert!(!html.contains("<style"));
+        assert!(!html.contains(".field-error {"));
+        assert!(!config_to_json(&config).contains("external_css"));
+    }
+
+    #[test]
+    fn test_segmented_groups() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+            /// Show line numbers
+            #[arg(long)]
+            numbers: bool,
+            /// Use color
+            #[arg(long, action = clap::ArgAction::Set, default_value = "true")]
+            color: bool,
+            /// Verbose output
+            #[arg(long)]
+            verbose: bool,
+        }
+
+        let mut config = test_config(extract_field_descriptors_from_command(&TestArgs::command()));
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"class="segmented""#));
+
+        // "name" isn't a bool, so it keeps its own input
+        config.segmented_groups = vec![vec!["color".to_string(), "numbers".to_string(), "name".to_string()]];
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches(r#"<div class="segmented" role="group">"#).count(), 1);
+        assert!(html.contains(concat!(
+            r#"<div class="field-group segmented-group"><div class="segmented" role="group">"#,
+            r#"<label class="segment" title="Show line numbers" data-field-name="numbers"><input type="checkbox" id="numbers" name="numbers"><span>numbers</span></label>"#,
+            r#"<label class="segment" title="Use color" data-field-name="color"><input type="checkbox" id="color" name="color" checked><span>color</span></label>"#,
+            r#"</div></div>"#,
+        )));
+        assert!(html.contains(r#"<input type="text" id="name""#));
+        assert!(html.contains(r#"<input type="checkbox" id="verbose" name="verbose">"#));
+        assert!(html.contains(r#""segmented_groups":[["color","numbers","name"]]"#));
+    }
+
+    #[test]
+    fn test_program_name() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct NamedArgs {
+            #[arg(long)]
+            verbose: bool,
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test", bin_name = "test-cli")]
+        struct BinNamedArgs {
+            #[arg(long)]
+            verbose: bool,
+        }
+
+        assert_eq!(program_name(&NamedArgs::command()), "test");
+        assert_eq!(program_name(&BinNamedArgs::command()), "test-cli");
+
+        // Clap keeps an explicit bin_name for its messages
+        let program = program_name(&BinNamedArgs::command());
+        let err = BinNamedArgs::try_parse_from([program.as_str(), "--bogus"]).err().unwrap();
+        assert!(err.to_string().contains("Usage: test-cli [OPTIONS]"));
+    }
+
+    #[test]
+    fn test_confirm_run() {
+        let mut config = test_config(vec![]);
+        assert!(config_to_json(&config).contains(r#""confirm_run":null"#));
+
+        // The message reaches the page's config as-is, quotes escaped
+        config.confirm_run = Some("This deletes the \"prod\" database.".to_string());
+        assert!(config_to_json(&config).contains(r#""confirm_run":"This deletes the \"prod\" database.""#));
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(&format!("window.CLI_CONFIG = {};", config_to_json(&config))));
+    }
+
+    #[test]
+    fn test_multi_line_help() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Output format
+            ///
+            /// Supported formats:
+            /// - json
+            /// - yaml
+            ///
+            /// Defaults to json.
+            #[arg(long, verbatim_doc_comment)]
+            format: Option<String>,
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+            #[arg(long, help = "Log level", long_help = "Log level\n\nOne of:\n- info\n- debug")]
+            level: Option<String>,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        // The help stays the summary; verbatim_doc_comment keeps the line
+        // breaks of the long help that clap would reflow
+        assert_eq!(fields[0].help, "Output format");
+        assert!(fields[0].long_help.as_deref().unwrap().starts_with("Output format\n\nSupported formats:\n- json\n"));
+        assert_eq!(fields[1].long_help, None);
+
+        let mut config = test_config(fields);
+        let description = concat!(
+            r#"<div class="field-description"><p>Supported formats:</p><ul><li>json</li><li>yaml</li></ul>"#,
+            r#"<p>Defaults to json.</p></div>"#,
+        );
+
+        // The first line is the label, the rest renders below it
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<label for="format">Output format</label>"#));
+        assert!(html.contains(description));
+        assert!(html.contains(r#"<label for="name">Name</label><span class="help-text"> (--name)</span><input"#));
+        // An explicit long_help only adds the description; the label and the
+        // config keep the help
+        assert!(html.contains(concat!(
+            r#"<label for="level">Log level</label><span class="help-text"> (--level)</span>"#,
+            r#"<div class="field-description"><p>One of:</p><ul><li>info</li><li>debug</li></ul></div>"#,
+        )));
+        assert!(html.contains(r#""name":"level","short":null,"long":"level","help":"Log level","field_type""#));
+
+        config.label_style = LabelStyle::NameWithDescription;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<div class="field-description"><p>Output format</p><p>Supported formats:</p><ul>"#));
+        assert!(html.contains(r#"<span class="field-description">Name</span>"#));
+
+        // Lines without blank lines between them keep their breaks
+        assert_eq!(
+            help_description("First\nSecond\n* third", false).into_string(),
+            r#"<div class="field-description"><p>First<br>Second</p><ul><li>third</li></ul></div>"#,
+        );
+    }
+
+    #[test]
+    fn test_inline_layout() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Query
+            query: String,
+            /// Limit
+            #[arg(long, default_value = "10")]
+            limit: u32,
+        }
+
+        let mut config = test_config(extract_field_descriptors_from_command(&TestArgs::command()));
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<form id="cliForm">"#));
+        assert!(html.contains(r#"<textarea id="query""#));
+
+        config.layout = Layout::Inline;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<form id="cliForm" class="inline-form">"#));
+        assert!(html.contains(r#"<input type="text" id="query" name="query" value="" placeholder="Query" required>"#));
+        assert!(html.contains(r#""layout":"Inline""#));
+
+        // Auto goes inline for up to INLINE_MAX_FIELDS simple fields
+        config.layout = Layout::Auto;
+        assert!(generate_wasm_function_page(&config).contains(r#"<form id="cliForm" class="inline-form">"#));
+        config.fields.push(FieldDescriptor {
+            name: "verbose".to_string(),
+            long: Some("verbose".to_string()),
+            field_type: FieldType::Bool,
+            ..Default::default()
+        });
+        assert!(generate_wasm_function_page(&config).contains(r#"<form id="cliForm">"#));
+        config.fields.pop();
+        config.extra_args = true;
+        assert!(generate_wasm_function_page(&config).contains(r#"<form id="cliForm">"#));
+    }
+
+    #[test]
+    fn test_args_breakdown() {
+        let mut config = test_config(vec![]);
+        assert!(!generate_wasm_function_page(&config).contains(r#"id="argsBreakdown""#));
+
+        config.args_breakdown = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<div class="args-breakdown"><label data-i18n="argsBreakdown">Args breakdown</label>"#,
+            r#"<table id="argsBreakdown"><thead><tr><th data-i18n="argFlag">Flag</th><th data-i18n="argValue">Value</th>"#,
+            r#"<th data-i18n="argSource">Field</th></tr></thead><tbody></tbody></table></div>"#,
+        )));
+        assert!(html.contains(r#""args_breakdown":true"#));
+
+        // A field can't take the table's id
+        config.fields.push(FieldDescriptor {
+            name: "argsBreakdown".to_string(),
+            long: Some("args".to_string()),
+            ..Default::default()
+        });
+        assert!(config.validate().unwrap_err().iter().any(|e| matches!(e, ConfigError::ConflictingId { .. })));
+    }
+
+    #[test]
+    fn test_require_equals() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Color mode
+            #[arg(long, require_equals = true)]
+            color: Option<String>,
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        assert!(fields.iter().find(|f| f.name == "color").unwrap().require_equals);
+        assert!(!fields.iter().find(|f| f.name == "name").unwrap().require_equals);
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#""name":"color","short":null,"long":"color""#));
+        assert!(html.contains(r#""require_equals":true"#));
+        assert!(html.contains("return [`${flag}=${values[0]}`, ...values.slice(1)];"));
+    }
+
+    #[test]
+    fn test_textarea_fields() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Script to run
+            #[arg(long, default_value = "echo hi")]
+            script: String,
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+        }
+
+        let mut config = test_config(extract_field_descriptors_from_command(&TestArgs::command()));
+        assert!(generate_wasm_function_page(&config).contains(r#"<input type="text" id="script""#));
+
+        config.textarea_fields.insert("script".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<textarea id="script" name="script" placeholder="Script to run" rows="5">echo hi</textarea>"#));
+        assert!(html.contains(r#"<input type="text" id="name""#));
+        assert!(html.contains(r#""textarea_fields":["script"]"#));
+    }
+
+    #[test]
+    fn test_last_positional() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Program to run
+            program: String,
+            /// Arguments passed through to the program
+            #[arg(last = true)]
+            rest: Option<String>,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let rest = fields.iter().find(|f| f.name == "rest").unwrap();
+        assert!(rest.last);
+        assert!(rest.is_positional);
+        assert!(matches!(rest.field_type, FieldType::Vec));
+        assert!(!fields.iter().find(|f| f.name == "program").unwrap().last);
+
+        let config = test_config(fields);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"id="rest-items""#));
+        assert!(html.contains(r#""is_positional":true,"index":2"#));
+        assert!(html.contains(r#""last":true"#));
+        assert!(html.contains("args.push('--');"));
+    }
+
+    #[test]
+    fn test_meta_description() {
+        use clap::Parser;
+
+        /// Checks rhymes & more
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[arg(long)]
+            name: Option<String>,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Rhyme Checker", "run_bind");
+        assert_eq!(config.meta_description.as_deref(), Some("Checks rhymes & more"));
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<title>Rhyme Checker</title><meta name="description" content="Checks rhymes &amp; more">"#,
+            r#"<meta property="og:title" content="Rhyme Checker">"#,
+            r#"<meta property="og:description" content="Checks rhymes &amp; more"><style>"#,
+        )));
+        assert!(!config_to_json(&config).contains("meta_description"));
+
+        config.html_shell = Some("<head>{{META}}</head><body>{{FORM}}{{SCRIPT}}</body>".to_string());
+        assert!(generate_wasm_function_page(&config).starts_with(r#"<head><meta name="description" content="Checks rhymes &amp; more">"#));
+
+        // No tags without about text
+        config.html_shell = None;
+        config.meta_description = Some("  ".to_string());
+        assert!(!generate_wasm_function_page(&config).contains("<meta name=\"description\""));
+    }
+
+    #[test]
+    fn test_enum_buttons() {
+        use clap::{Parser, CommandFactory, ValueEnum};
+
+        #[derive(Clone, ValueEnum)]
+        enum Speed {
+            /// Skip the checks
+            Fast,
+            Safe,
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Speed
+            #[arg(long, value_enum)]
+            speed: Option<Speed>,
+            /// Level
+            #[arg(long, default_value = "two", value_parser = ["one", "two", "three"])]
+            level: String,
+        }
+
+        let mut config = test_config(extract_field_descriptors_from_command(&TestArgs::command()));
+        assert!(!generate_wasm_function_page(&config).contains(r#"class="enum-buttons""#));
+
+        // Only enums within the limit become buttons
+        config.enum_buttons = Some(2);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<div class="enum-buttons" id="speed" role="radiogroup">"#,
+            r#"<label class="enum-button"><input type="radio" name="speed" value="" checked><span data-i18n="noneOption">None</span></label>"#,
+            r#"<label class="enum-button" title="Skip the checks"><input type="radio" name="speed" value="fast"><span>Fast</span></label>"#,
+            r#"<label class="enum-button"><input type="radio" name="speed" value="safe"><span>Safe</span></label></div>"#,
+        )));
+        assert!(html.contains(r#"<select id="level""#));
+
+        // A default preselects its button, with no "None" button
+        config.enum_buttons = Some(4);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<div class="enum-buttons" id="level" role="radiogroup"><label class="enum-button"><input type="radio" name="level" value="one">"#));
+        assert!(html.contains(r#"<input type="radio" name="level" value="two" checked><span>Two</span>"#));
+        assert!(html.contains(r#""enum_buttons":4"#));
+    }
+
+    #[test]
+    fn test_default_overrides() {
+        use clap::{Parser, Subcommand, ValueEnum};
+
+        #[derive(Clone, ValueEnum)]
+        enum Mode {
+            Fast,
+            Safe,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            Copy {
+                /// Retries
+                #[arg(long, default_value = "3")]
+                retries: u32,
+            },
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Mode
+            #[arg(long, value_enum, default_value = "fast")]
+            mode: Mode,
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Test", "run_bind");
+        config.default_overrides.insert("mode".to_string(), "safe".to_string());
+        config.default_overrides.insert("name".to_string(), "web".to_string());
+        config.default_overrides.insert("retries".to_string(), "1".to_string());
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<option value="safe" selected>Safe</option>"#));
+        assert!(html.contains(r#"<option value="fast">Fast</option>"#));
+        assert!(html.contains(r#"<input type="text" id="name" name="name" value="web""#));
+        assert!(html.contains(r#"<input type="number" id="copy-retries" name="copy-retries" value="1""#));
+
+        // The JSON carries the overridden defaults, the extracted fields are untouched
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        assert_eq!(json["fields"][0]["default_value"], "safe");
+        assert_eq!(json["subcommands"][0]["fields"][0]["default_value"], "1");
+        assert!(json.get("default_overrides").is_none());
+        assert_eq!(config.fields[0].default_value.as_deref(), Some("fast"));
+    }
+
+    #[test]
+    fn test_external_wasm() {
+        let mut config = WasmFunctionConfig {
+            function_name: "run_bind".to_string(),
+            package_name: "test".to_string(),
+            page_title: "Test".to_string(),
+            ..Default::default()
+        };
+        assert!(generate_wasm_function_page(&config).contains(r#""external_wasm":false"#));
+
+        config.external_wasm = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#""external_wasm":true"#));
+        assert!(html.contains("wasm = await window.wasmModule;"));
+        assert!(html.contains("typeof wasm.run_bind !== 'function'"));
+    }
+
+    #[test]
+    fn test_global_help_headings() {
+        use clap::{CommandFactory, Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Print more output
+            #[arg(long, global = true, help_heading = "Output")]
+            verbose: bool,
+            /// Output color
+            #[arg(long, global = true, help_heading = "Output")]
+            color: Option<String>,
+            #[arg(long)]
+            name: Option<String>,
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            Add {
+                #[arg(long)]
+                item: String,
+            },
+        }
+
+        // Globals are only copied into subcommands once the command is built
+        let mut built = TestArgs::command();
+        built.build();
+        for cmd in [TestArgs::command(), built] {
+            let fields = extract_field_descriptors_from_command(&cmd);
+            let verbose = fields.iter().find(|f| f.name == "verbose").unwrap();
+            assert_eq!(verbose.help_heading.as_deref(), Some("Output"));
+            assert_eq!(fields.iter().find(|f| f.name == "name").unwrap().help_heading, None);
+
+            let subcommands = extract_subcommands_from_command(&cmd);
+            let add: Vec<&str> = subcommands[0].fields.iter().map(|f| f.name.as_str()).collect();
+            assert_eq!(add, ["item"]);
+        }
+
+        let config = build_config_for_parser::<TestArgs>("test", "Test", "run");
+        let html = generate_wasm_function_page(&config);
+        // Both globals render once, together under their heading
+        assert_eq!(html.matches("<legend>Output</legend>").count(), 1);
+        assert_eq!(html.matches(r#"id="verbose""#).count(), 1);
+        assert!(!html.contains(r#"id="add-verbose""#));
+        let group = &html[html.find(r#"<fieldset class="heading-group">"#).unwrap()..];
+        let group = &group[..group.find("</fieldset>").unwrap()];
+        assert!(group.contains(r#"id="verbose""#));
+        assert!(group.contains(r#"id="color""#));
+        assert!(!group.contains(r#"id="name""#));
+    }
+
+    #[test]
+    fn test_output_line_numbers() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<pre id="output" data-i18n="noOutputYet">"#));
+        assert!(config_to_json(&config).contains(r#""output_line_numbers":false"#));
+
+        // The class draws the gutter, the config has cli-ui.js number the lines
+        config.output_line_numbers = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<pre id="output" class="line-numbers" data-i18n="noOutputYet">"#));
+        assert!(config_to_json(&config).contains(r#""output_line_numbers":true"#));
+
+        config.output_mode = OutputMode::Append;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<pre id="output" class="terminal line-numbers" data-i18n="noOutputYet">"#));
+    }
+
+    #[test]
+    fn test_open_ended_num_args() {
+        use clap::{CommandFactory, Parser};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Files to check
+            #[arg(required = true)]
+            files: Vec<String>,
+            #[arg(long, num_args(1..))]
+            tags: Vec<String>,
+            #[arg(long, num_args(2..))]
+            pairs: Vec<String>,
+            #[arg(long, num_args(1..=3))]
+            some: Vec<String>,
+            #[arg(long)]
+            name: Option<String>,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let min_values = |name: &str| fields.iter().find(|f| f.name == name).unwrap().min_values;
+        assert_eq!(min_values("files"), Some(1));
+        assert_eq!(min_values("tags"), Some(1));
+        assert_eq!(min_values("pairs"), Some(2));
+        assert_eq!(min_values("some"), None);
+        assert_eq!(min_values("name"), None);
+        assert!(matches!(fields.iter().find(|f| f.name == "tags").unwrap().field_type, FieldType::Vec));
+
+        // The minimums reach the page's config, which checks them before Run
+        let config = test_config(fields);
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        let field = |name: &str| json["fields"].as_array().unwrap().iter().find(|f| f["name"] == name).unwrap().clone();
+        assert_eq!(field("files")["required"], true);
+        assert_eq!(field("files")["min_values"], 1);
+        assert_eq!(field("pairs")["min_values"], 2);
+        assert_eq!(field("some")["min_values"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_field_data_attributes() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[arg(short = 'n', long, default_value = "3")]
+            count: u32,
+            #[arg(long)]
+            verbose: bool,
+            /// Input file
+            input: String,
+            #[arg(long)]
+            tag: Vec<String>,
+            #[arg(short, action = clap::ArgAction::Count)]
+            quiet: u8,
+            #[arg(long, value_parser = ["fast", "slow"])]
+            speed: Option<String>,
+            #[arg(long, value_hint = clap::ValueHint::FilePath)]
+            config: Option<std::path::PathBuf>,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Test", "run");
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("data-field-type"));
+
+        config.field_data_attributes = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<div class="field-group" data-field-name="count" data-is-positional="false" "#,
+            r#"data-field-type="Integer" data-default="3" data-required="false" data-short="n" data-long="count">"#,
+        )));
+        assert!(html.contains(concat!(
+            r#"<div class="field-group checkbox-group" data-field-name="verbose" data-is-positional="false" "#,
+            r#"data-field-type="Bool" data-required="false" data-long="verbose">"#,
+        )));
+        assert!(html.contains(concat!(
+            r#"<div class="field-group" data-field-name="input" data-is-positional="true" "#,
+            r#"data-field-type="String" data-required="true">"#,
+        )));
+        // Every field type's group carries them
+        for field_type in ["Vec", "Counter", "Enum", "Path"] {
+            assert!(html.contains(&format!(r#"data-field-type="{}""#, field_type)));
+        }
+        let groups = html.matches(r#"<div class="field-group"#).count();
+        assert_eq!(groups, 7);
+        assert_eq!(html.matches("data-field-type=").count(), groups);
+    }
+
+    #[test]
+    fn test_reset_buttons() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[arg(long, default_value = "3")]
+            count: u32,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Test", "run");
+        assert!(config_to_json(&config).contains(r#""reset_buttons":false"#));
+
+        // The buttons are added by the script, from the defaults in the config
+        config.reset_buttons = true;
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        assert_eq!(json["reset_buttons"], true);
+        assert_eq!(json["fields"][0]["default_value"], "3");
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"id="count" name="count" value="3""#));
+    }
+
+    #[test]
+    fn test_value_terminator() {
+        use clap::{CommandFactory, Parser};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Command to run on each file
+            #[arg(long, num_args(1..), value_terminator = ";")]
+            exec: Vec<String>,
+            /// Files to check
+            files: Vec<String>,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let exec = fields.iter().find(|f| f.name == "exec").unwrap();
+        assert_eq!(exec.value_terminator.as_deref(), Some(";"));
+        assert!(matches!(exec.field_type, FieldType::Vec));
+        assert_eq!(fields.iter().find(|f| f.name == "files").unwrap().value_terminator, None);
+
+        // The page's config carries the terminator that arg-builder.js emits
+        let config = test_config(fields);
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        assert_eq!(json["fields"][0]["name"], "exec");
+        assert_eq!(json["fields"][0]["value_terminator"], ";");
+        assert_eq!(json["fields"][1]["value_terminator"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_bare_page() {
+        let mut config = WasmFunctionConfig {
+            description: Some("Does things".to_string()),
+            external_css: Some("cli-ui.css".to_string()),
+            ..test_config(vec![])
+        };
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<link rel="stylesheet" href="cli-ui.css">"#));
+        assert!(html.contains(r#"<div class="container"><div class="header-row">"#));
+
+        config.bare = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("<style"));
+        assert!(!html.contains(r#"rel="stylesheet""#));
+        assert!(!html.contains(r#"class="container""#));
+        assert!(!html.contains("language-selector\""));
+        assert!(!html.contains("<p class=\"description\">"));
+        assert!(html.contains(r#"<title>Test</title></head><body><form id="cliForm">"#));
+        assert!(html.contains(r#"<pre id="output" data-i18n="noOutputYet">"#));
+        assert!(html.contains("window.CLI_CONFIG = "));
+    }
+
+    #[test]
+    fn test_inline_code() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Same as `--all <x>`
+            #[arg(long)]
+            every: bool,
+            /// Output format
+            ///
+            /// Use `json` for scripts
+            #[arg(long)]
+            format: Option<String>,
+            /// Unpaired ` backtick
+            #[arg(long)]
+            odd: Option<String>,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Test", "run");
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains("Same as `--all &lt;x&gt;`"));
+        assert!(!html.contains("<code>"));
+
+        config.inline_code = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<input type="checkbox" id="every" name="every">Same as <code>--all &lt;x&gt;</code></label>"#));
+        assert!(html.contains(r#"<span class="field-description">Use <code>json</code> for scripts</span>"#));
+        assert!(html.contains(r#"<label for="odd">Unpaired ` backtick</label>"#));
+    }
+
+    #[test]
+    fn test_run_history() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"id="runHistory""#));
+        assert!(config_to_json(&config).contains(r#""run_history":false,"run_history_size":null,"persist_run_history":false"#));
+
+        config.run_history = true;
+        config.run_history_size = Some(3);
+        config.persist_run_history = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<div class="run-history"><select id="runHistory"><option value="" data-i18n="previousRuns">Previous runs</option></select>"#,
+            r#"<button id="restoreRunButton" type="button" data-i18n="restoreForm" disabled>Restore form</button></div>"#,
+            r#"<pre id="output""#,
+        )));
+        assert!(config_to_json(&config).contains(r#""run_history":true,"run_history_size":3,"persist_run_history":true"#));
+
+        // The ids can't be taken by fields
+        config.fields.push(FieldDescriptor { name: "runHistory".to_string(), ..Default::default() });
+        assert!(config.validate().unwrap_err().contains(&ConfigError::ConflictingId { id: "runHistory".to_string() }));
+    }
+
+    #[test]
+    fn test_shared_subcommand_fields() {
+        use clap::{Args, Parser, Subcommand};
+
+        #[derive(Args)]
+        struct Common {
+            /// Print more output
+            #[arg(long)]
+            verbose: bool,
+            /// Output format
+            #[arg(long, default_value = "text")]
+            format: String,
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            Add {
+                #[command(flatten)]
+                common: Common,
+                name: String,
+                #[arg(long)]
+                limit: Option<u32>,
+            },
+            Remove {
+                #[command(flatten)]
+                common: Common,
+                /// A different limit
+                #[arg(long)]
+                limit: Option<u32>,
+            },
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Test", "run");
+        let shared: Vec<String> = shared_subcommand_fields(&config.subcommands).into_iter().map(|f| f.name).collect();
+        // `limit` has different help text, and `name` is positional
+        assert_eq!(shared, ["verbose", "format"]);
+
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("subcommand-__shared__"));
+        assert!(html.contains(r#"id="add-verbose""#));
+
+        config.shared_subcommand_fields = true;
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches(r#"<div class="subcommand-fields" id="subcommand-__shared__""#).count(), 1);
+        assert!(html.contains(r#"<h3 data-i18n="sharedOptions">Options for every subcommand</h3>"#));
+        assert!(html.contains(r#"id="__shared__-verbose""#));
+        assert!(html.contains(r#"id="__shared__-format""#));
+        assert!(!html.contains(r#"id="add-verbose""#));
+        assert!(!html.contains(r#"id="remove-format""#));
+        assert!(html.contains(r#"id="add-limit""#));
+        assert!(html.contains(r#"id="remove-limit""#));
+        assert!(html.contains(r#"id="add-name""#));
+        // Each subcommand keeps its shared fields in the config; the script maps
+        // them to the shared inputs
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        assert_eq!(json["shared_subcommand_fields"], true);
+        assert_eq!(json["subcommands"][1]["fields"][0]["name"], "verbose");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_html() {
+        assert_eq!(validate_html("<!DOCTYPE html><html><!-- note <b> --><body a=1 b='2' c></body></html>"), Ok(()));
+        // Raw text may contain markup-like text
+        assert_eq!(validate_html("<script>if (a < b) x = '</span>';</script><br/>"), Ok(()));
+
+        let errors = |html: &str| validate_html(html).unwrap_err();
+        assert_eq!(errors("<div><p>text</div>"), [HtmlError::UnclosedTag { tag: "p".to_string(), offset: 5 }]);
+        assert_eq!(errors("<p></p></span>"), [HtmlError::UnexpectedClosingTag { tag: "span".to_string(), offset: 7 }]);
+        assert_eq!(errors("<main><div>"), [
+            HtmlError::UnclosedTag { tag: "main".to_string(), offset: 0 },
+            HtmlError::UnclosedTag { tag: "div".to_string(), offset: 6 },
+        ]);
+        assert_eq!(errors(r#"<a href="x>link</a>"#), [HtmlError::Unterminated { offset: 8 }]);
+        assert_eq!(errors("<p class=>x</p>"), [HtmlError::InvalidAttribute { tag: "p".to_string(), offset: 9 }]);
+        assert_eq!(errors(r#"<p class="a"">x</p>"#), [HtmlError::InvalidAttribute { tag: "p".to_string(), offset: 12 }]);
+        assert_eq!(errors("<div"), [HtmlError::Unterminated { offset: 0 }]);
+        assert_eq!(errors("<style>p {}"), [HtmlError::UnclosedTag { tag: "style".to_string(), offset: 0 }]);
+    }
+
+    #[test]
+    fn test_generated_pages_are_well_formed() {
+        use clap::{Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "test", about = "Checks things", after_help = "Examples:\n  $ test --name a add x\n")]
+        struct TestArgs {
+            /// Your `name`
+            #[arg(short, long, default_value = "bob")]
+            name: String,
+            #[arg(long)]
+            tags: Vec<String>,
+            #[command(subcommand)]
+            command: Option<Commands>,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Add an item
+            Add { item: String },
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "", "run");
+        config.args_breakdown = true;
+        config.run_history = true;
+        config.inline_code = true;
+        assert_eq!(validate_html(&generate_wasm_function_page(&config)), Ok(()));
+
+        config.web_component = true;
+        assert_eq!(validate_html(&generate_wasm_function_page(&config)), Ok(()));
+
+        config.web_component = false;
+        config.bare = true;
+        config.output_mode = OutputMode::Append;
+        assert_eq!(validate_html(&generate_wasm_function_page(&config)), Ok(()));
+    }
+
+    #[test]
+    fn test_allow_hyphen_values() {
+        use clap::{CommandFactory, Parser};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Offset to apply, may be negative
+            #[arg(long, allow_hyphen_values = true)]
+            offset: Option<String>,
+            /// Plain name
+            #[arg(long)]
+            name: Option<String>,
+            /// Expression to evaluate
+            #[arg(allow_hyphen_values = true)]
+            expr: Option<String>,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let find = |name: &str| fields.iter().find(|f| f.name == name).unwrap();
+        assert!(find("offset").allow_hyphen_values);
+        assert!(!find("name").allow_hyphen_values);
+        assert!(find("expr").allow_hyphen_values);
+
+        // A hyphen-leading value such as `-5` goes in as `--offset=-5`
+        let parsed = TestArgs::try_parse_from(["test", "--offset=-5", "-x"]).unwrap();
+        assert_eq!(parsed.offset.as_deref(), Some("-5"));
+        assert_eq!(parsed.expr.as_deref(), Some("-x"));
+
+        let config = test_config(fields);
+        let json: serde_json::Value = serde_json::from_str(&config_to_json(&config)).unwrap();
+        let allowed: Vec<&str> = json["fields"].as_array().unwrap().iter()
+            .filter(|f| f["allow_hyphen_values"] == true)
+            .map(|f| f["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(allowed, ["offset", "expr"]);
+    }
+
+    #[test]
+    fn test_render_fields_html() {
+        use clap::{CommandFactory, Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Verbose output
+            #[arg(short, long)]
+            verbose: bool,
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Add a file
+            Add {
+                /// File to add
+                file: String,
+            },
+        }
+
+        let command = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&command);
+        let subcommands = extract_subcommands_from_command(&command);
+        let fields_html = render_fields_html(&fields);
+        let subcommands_html = render_subcommand_fields_html(&subcommands);
+        assert!(fields_html.contains(r#"id="verbose""#));
+        assert!(!fields_html.contains("<html"));
+        assert!(subcommands_html.contains(r#"id="subcommand-selector""#));
+        assert!(subcommands_html.contains(r#"id="add-file""#));
+
+        // The fragments match the fields of a full page
+        let config = WasmFunctionConfig {
+            subcommands,
+            ..test_config(fields)
+        };
+        let page = generate_wasm_function_page(&config);
+        assert!(page.contains(&fields_html));
+        assert!(page.contains(&subcommands_html));
+        assert!(validate_html(&fields_html).is_ok());
+        assert!(validate_html(&subcommands_html).is_ok());
+    }
+
+    #[test]
+    fn test_uuid_inputs() {
+        use clap::{CommandFactory, Parser};
+
+        /// Stands in for `uuid::Uuid`; detection goes by the type name
+        #[derive(Clone, Debug)]
+        struct Uuid;
+
+        impl std::str::FromStr for Uuid {
+            type Err = String;
+
+            fn from_str(_: &str) -> Result<Self, Self::Err> {
+                Ok(Uuid)
+            }
+        }
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Session to resume
+            #[arg(long)]
+            session: Uuid,
+            /// Session name
+            #[arg(long)]
+            name: String,
+        }
+
+        let fields = extract_field_descriptors_from_command(&TestArgs::command());
+        let session = fields.iter().find(|f| f.name == "session").unwrap();
+        assert!(session.is_uuid);
+        assert!(matches!(session.field_type, FieldType::String));
+        assert!(!fields.iter().find(|f| f.name == "name").unwrap().is_uuid);
+
+        let mut config = test_config(fields);
+        let pattern = format!(r#"pattern="{}""#, UUID_PATTERN);
+
+        // Detected UUIDs render as plain text inputs unless enabled
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(&pattern));
+
+        config.uuid_inputs = true;
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches(&pattern).count(), 1);
+        assert!(html.contains(&format!(r#"placeholder="{}""#, UUID_PLACEHOLDER)));
+        assert!(html.contains(r#"placeholder="Session name""#));
+    }
+
+    #[test]
+    fn test_required_summary() {
+        let mut config = test_config(vec![FieldDescriptor {
+            name: "input".to_string(),
+            long: Some("input".to_string()),
+            required: true,
+            ..Default::default()
+        }]);
+        assert!(!generate_wasm_function_page(&config).contains(r#"id="requiredSummary""#));
+
+        config.required_summary = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<span class="required-summary" id="requiredSummary"><span class="required-filled">0</span>/"#,
+            r#"<span class="required-total">0</span> "#,
+            r#"<span data-i18n="requiredFieldsFilled">required fields filled</span></span>"#,
+        )));
+        assert!(html.contains("runButton.disabled = filled < total;"));
+        assert!(html.contains(r#""required_summary":true"#));
+        assert!(validate_html(&html).is_ok());
+
+        // Its id is reserved
+        config.fields[0].name = "requiredSummary".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_non_utf8_default() {
+        use clap::{Arg, Command};
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let cmd = Command::new("test")
+            .arg(Arg::new("dir").long("dir").default_value(OsStr::from_bytes(b"caf\xe9")))
+            .arg(Arg::new("name").long("name").default_value("cafe"))
+            .subcommand(Command::new("add")
+                .arg(Arg::new("path").long("path").default_value(OsStr::from_bytes(b"\xff"))));
+
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let dir = fields.iter().find(|f| f.name == "dir").unwrap();
+        assert!(dir.non_utf8_default);
+        assert_eq!(dir.default_value, None);
+        let name = fields.iter().find(|f| f.name == "name").unwrap();
+        assert!(!name.non_utf8_default);
+        assert_eq!(name.default_value.as_deref(), Some("cafe"));
+
+        let config = WasmFunctionConfig {
+            subcommands: extract_subcommands_from_command(&cmd),
+            ..test_config(fields)
+        };
+        assert_eq!(config.warnings(), vec![
+            ConfigWarning::NonUtf8Default { subcommand: None, name: "dir".to_string() },
+            ConfigWarning::NonUtf8Default { subcommand: Some("add".to_string()), name: "path".to_string() },
+        ]);
+        assert_eq!(
+            config.warnings()[1].to_string(),
+            "field 'path' of subcommand 'add' has a default that isn't valid UTF-8; the form leaves it out"
+        );
+
+        // The input is left empty, with a placeholder saying why
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<input type="text" id="dir" name="dir" value="" "#,
+            r#"placeholder="Default can't be shown (not valid UTF-8)" data-i18n="nonUtf8Default">"#,
+        )));
+        assert!(html.contains(r#"<input type="text" id="name" name="name" value="cafe" placeholder="#));
+    }
+
+    #[test]
+    fn test_breadcrumb() {
+        use clap::{Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "tool", bin_name = "my-tool")]
+        struct TestArgs {
+            #[command(subcommand)]
+            command: Commands,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            /// Add a file
+            Add,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("tool", "Tool", "run_bind");
+        assert_eq!(config.command_name.as_deref(), Some("my-tool"));
+        assert!(!generate_wasm_function_page(&config).contains(r#"id="breadcrumb""#));
+
+        config.breadcrumb = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<nav class="breadcrumb" id="breadcrumb"><span class="breadcrumb-item">my-tool</span></nav>"#,
+            r#"<form id="cliForm">"#,
+        )));
+        // The selector's value, the subcommand's name, is appended when chosen
+        assert!(html.contains(r#"<option value="add">Add a file (add)</option>"#));
+        assert!(config_to_json(&config).contains(r#""breadcrumb":true"#));
+        assert!(!config_to_json(&config).contains("command_name"));
+
+        // Hand-written configs fall back to the page title
+        config.command_name = None;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<span class="breadcrumb-item">Tool</span>"#));
+    }
+
+    #[test]
+    fn test_advanced_fields() {
+        let field = |name: &str, required: bool| FieldDescriptor {
+            name: name.to_string(),
+            long: Some(name.to_string()),
+            required,
+            ..Default::default()
+        };
+        let mut config = WasmFunctionConfig {
+            subcommands: vec![SubcommandDescriptor {
+                name: "add".to_string(),
+                fields: vec![field("name", true), field("token", false)],
+                ..Default::default()
+            }],
+            ..test_config(vec![field("input", true), field("token", true), field("verbose", false)])
+        };
+        assert!(!generate_wasm_function_page(&config).contains(r#"<details class="advanced-options""#));
+
+        // Listed fields are collapsed even when required, in subcommands too
+        config.advanced_fields.insert("token".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches(r#"<details class="advanced-options">"#).count(), 2);
+        let details = html.find(r#"<details class="advanced-options">"#).unwrap();
+        assert!(html.find(r#"id="input""#).unwrap() < details);
+        assert!(html.find(r#"id="verbose""#).unwrap() < details);
+        assert!(html.find(r#"id="token""#).unwrap() > details);
+        assert!(html.contains(r#"<input type="text" id="token" name="token" value="" placeholder="token" required>"#));
+
+        // With collapsed optional fields they share the section, after them
+        config.collapse_optional_fields = true;
+        let html = generate_wasm_function_page(&config);
+        let details = html.find(r#"<details class="advanced-options">"#).unwrap();
+        assert!(html.find(r#"id="input""#).unwrap() < details);
+        assert!(details < html.find(r#"id="verbose""#).unwrap());
+        assert!(html.find(r#"id="verbose""#).unwrap() < html.find(r#"id="token""#).unwrap());
+
+        // Failed validation opens the section holding an invalid field
+        assert!(html.contains("form.querySelectorAll('details .error').forEach(el => {"));
+    }
+
+    #[test]
+    fn test_subcommand_tabs() {
+        let mut config = WasmFunctionConfig {
+            subcommands: vec![
+                SubcommandDescriptor {
+                    name: "add".to_string(),
+                    help: "Add a file".to_string(),
+                    ..Default::default()
+                },
+                SubcommandDescriptor { name: "remove".to_string(), ..Default::default() },
+            ],
+            allow_external_subcommands: true,
+            ..test_config(vec![])
+        };
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<select id="subcommand-selector" name="subcommand">"#));
+        assert!(!html.contains(r#"class="subcommand-tabs""#));
+
+        config.subcommand_style = SubcommandStyle::Tabs;
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains("<select id=\"subcommand-selector\""));
+        assert!(html.contains(concat!(
+            r#"<div class="subcommand-tabs" id="subcommand-selector" role="tablist" aria-label="Subcommands">"#,
+            r#"<button class="subcommand-tab" type="button" role="tab" id="subcommand-tab-add" "#,
+            r#"aria-controls="subcommand-add" aria-selected="false" tabindex="0" data-subcommand="add" "#,
+            r#"title="Add a file">add</button>"#,
+            r#"<button class="subcommand-tab" type="button" role="tab" id="subcommand-tab-remove" "#,
+            r#"aria-controls="subcommand-remove" aria-selected="false" tabindex="-1" data-subcommand="remove">remove</button>"#,
+        )));
+        assert!(html.contains(r#"data-subcommand="__external__"><span data-i18n="externalSubcommand">Other (custom)</span></button></div>"#));
+        assert!(html.contains(concat!(
+            r#"<div class="subcommand-fields" id="subcommand-add" data-subcommand="add" role="tabpanel" "#,
+            r#"aria-labelledby="subcommand-tab-add" hidden>"#,
+        )));
+        assert!(html.contains(r#"role="tabpanel" aria-labelledby="subcommand-tab-__external__""#));
+        assert!(html.contains(r#""subcommand_style":"Tabs""#));
+        assert!(validate_html(&html).is_ok());
+
+        // Tab ids can't be reused by fields
+        config.fields.push(FieldDescriptor { name: "subcommand-tab-add".to_string(), ..Default::default() });
+        assert_eq!(config.validate(), Err(vec![ConfigError::ConflictingId { id: "subcommand-tab-add".to_string() }]));
+    }
+
+    #[test]
+    fn test_head_extra() {
+        let mut config = test_config(vec![]);
+        let built_in = generate_wasm_function_page(&config);
+
+        // Inserted verbatim at the end of the head
+        let extra = r##"<link rel="icon" href="favicon.svg"><meta name="theme-color" content="#4CAF50">"##;
+        config.head_extra = Some(extra.to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(&format!("</style>{}</head>", extra)));
+        assert_eq!(html.replace(extra, ""), built_in);
+        assert!(!config_to_json(&config).contains("head_extra"));
+
+        // In a custom shell it follows the styles
+        config.html_shell = Some("<head>{{STYLES}}</head><body>{{FORM}}{{SCRIPT}}</body>".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(&format!("</style>{}</head>", extra)));
+    }
+
+    #[test]
+    fn test_int_ranges() {
+        let mut config = test_config(vec![FieldDescriptor {
+            name: "lines".to_string(),
+            long: Some("lines".to_string()),
+            help: "Lines to show".to_string(),
+            default_value: Some("1:20".to_string()),
+            ..Default::default()
+        }]);
+        assert!(!generate_wasm_function_page(&config).contains(r#"class="int-range""#));
+
+        config.int_ranges.insert("lines".to_string(), ":".to_string());
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<label for="lines-start">Lines to show</label>"#));
+        assert!(html.contains(concat!(
+            r#"<div class="int-range" id="lines" data-separator=":">"#,
+            r#"<input type="number" id="lines-start" name="lines-start" value="1" step="1" placeholder="Start" data-i18n="rangeStart">"#,
+            r#"<span class="int-range-separator">:</span>"#,
+            r#"<input type="number" id="lines-end" name="lines-end" value="20" step="1" placeholder="End" data-i18n="rangeEnd">"#,
+            r#"</div>"#,
+        )));
+        // The JS sees the type, and joins the inputs with the separator
+        let json = config_to_json(&config);
+        assert!(json.contains(r#""field_type":{"type":"IntRange"}"#));
+        assert!(json.contains(r#""int_ranges":{"lines":":"}"#));
+        assert!(validate_html(&html).is_ok());
+    }
+
+    #[test]
+    fn test_rtl_direction() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains("<html><head>"));
+
+        config.direction = Direction::Rtl;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<html dir="rtl">"#));
+        assert!(html.contains(r#"<div class="container" dir="rtl">"#));
+        assert!(html.contains(r#""direction":"Rtl""#));
+
+        // Bare output leaves the direction to the host page
+        config.bare = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"class="container""#));
+    }
+
+    #[test]
+    fn test_build_config_for_command() {
+        use clap::builder::PossibleValuesParser;
+        use clap::{Arg, ArgAction};
+
+        // Built the way a YAML/JSON definition would be loaded, without a Parser type
+        let cmd = Command::new("deploy")
+            .about("Deploy a service")
+            .arg(Arg::new("service").required(true).help("Service to deploy"))
+            .arg(
+                Arg::new("env")
+                    .long("env")
+                    .help("Target environment")
+                    .value_parser(PossibleValuesParser::new(["staging", "production"]))
+                    .default_value("staging"),
+            )
+            .arg(Arg::new("dry-run").long("dry-run").action(ArgAction::SetTrue))
+            .subcommand(
+                Command::new("rollback")
+                    .arg(Arg::new("steps").long("steps").value_parser(clap::value_parser!(u32))),
+            );
+
+        let config = build_config_for_command(&cmd, "deploy_pkg", "", "deploy_bind");
+        assert_eq!(config.page_title, "Deploy a service");
+        assert_eq!(config.command_name.as_deref(), Some("deploy"));
+
+        let env = config.fields.iter().find(|f| f.name == "env").unwrap();
+        let FieldType::Enum(options) = &env.field_type else { panic!("env should be an enum") };
+        assert_eq!(options.iter().map(|o| o.value.as_str()).collect::<Vec<_>>(), ["staging", "production"]);
+        assert!(matches!(config.fields.iter().find(|f| f.name == "dry-run").unwrap().field_type, FieldType::Bool));
+        assert!(config.fields.iter().find(|f| f.name == "service").unwrap().is_positional);
+        assert_eq!(config.subcommands.len(), 1);
+        assert!(matches!(config.subcommands[0].fields[0].field_type, FieldType::Integer));
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<select id="env" name="env">"#));
+        assert!(html.contains(r#"<option value="staging" selected>"#));
+        assert!(html.contains(r#"<option value="production">"#));
+        assert!(html.contains("./deploy_pkg.js") && html.contains("deploy_bind"));
+    }
+
+    #[test]
+    fn test_field_order_display_order() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Zeta
+            #[arg(long, display_order = 3)]
+            zeta: Option<String>,
+
+            /// Source
+            source: String,
+
+            /// Alpha
+            #[arg(long, display_order = 1)]
+            alpha: Option<String>,
+
+            /// Mid
+            #[arg(long, display_order = 2)]
+            mid: Option<String>,
+        }
+
+        let cmd = TestArgs::command();
+        let fields = extract_field_descriptors_from_command(&cmd);
+        let find = |name: &str| fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(find("alpha").display_order, Some(1));
+        assert_eq!(find("source").display_order, None);
+
+        let mut config = test_config(fields);
+
+        let order = |html: &str| {
+            let mut names: Vec<&str> = ["zeta", "source", "alpha", "mid"].into_iter().collect();
+            names.sort_by_key(|n| html.find(&format!("data-field-name=\"{}\"", n)).unwrap());
+            names
+        };
+
+        assert_eq!(order(&generate_wasm_function_page(&config)), ["zeta", "source", "alpha", "mid"]);
+
+        // Flags follow --help; the positional keeps its slot
+        config.field_order = FieldOrder::DisplayOrder;
+        assert_eq!(order(&generate_wasm_function_page(&config)), ["alpha", "source", "mid", "zeta"]);
+    }
+
+    #[test]
+    fn test_subcommand_required() {
+        let cmd = Command::new("tool")
+            .subcommand_required(true)
+            .subcommand(Command::new("add"))
+            .subcommand(Command::new("remove"));
+        let mut config = build_config_for_command(&cmd, "tool", "", "run_bind");
+        assert!(config.subcommand_required);
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<p class="subcommand-hint" data-i18n="chooseOneSubcommand">Choose one:</p>"#));
+        assert!(html.contains(r#"<span class="required-marker" aria-hidden="true">*</span>"#));
+        assert!(html.contains(r#"<select id="subcommand-selector" name="subcommand" required>"#));
+        assert!(html.contains(r#""subcommand_required":true"#));
+
+        // The hint also heads the tabs
+        config.subcommand_style = SubcommandStyle::Tabs;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<p class="subcommand-hint" data-i18n="chooseOneSubcommand">Choose one:</p><div class="subcommand-tabs""#));
+
+        let config = build_config_for_command(&cmd.subcommand_required(false), "tool", "", "run_bind");
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"class="subcommand-hint""#));
+        assert!(html.contains(r#"<select id="subcommand-selector" name="subcommand">"#));
+    }
+
+    #[test]
+    fn test_show_timing() {
+        let mut config = test_config(vec![]);
+        assert!(generate_wasm_function_page(&config).contains(r#""show_timing":false"#));
+
+        config.show_timing = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#""show_timing":true"#));
+        assert!(html.contains("started = performance.now();"));
+        assert!(html.contains("completedIn: 'Completed in {ms} ms'"));
+    }
+
+    #[test]
+    fn test_enum_icons() {
+        let cmd = Command::new("test")
+            .arg(Arg::new("speed").long("speed").value_parser(["fast", "safe"]).default_value("safe"));
+        let mut config = test_config(extract_field_descriptors_from_command(&cmd));
+        config.enum_icons.insert("fast".to_string(), "⚡".to_string());
+
+        // Options without an icon stay plain text
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<option value="fast">⚡ Fast</option>"#));
+        assert!(html.contains(r#"<option value="safe" selected>Safe</option>"#));
+        assert!(html.contains(r#""value":"fast","help":"","icon":"⚡""#));
+
+        config.enum_buttons = Some(4);
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<input type="radio" name="speed" value="fast">"#,
+            r#"<span><i class="enum-icon" aria-hidden="true">⚡</i>Fast</span>"#,
+        )));
+        assert!(html.contains(r#"<input type="radio" name="speed" value="safe" checked><span>Safe</span>"#));
+    }
+
+    #[test]
+    fn test_arg_builder_js() {
+        let config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+
+        // The page embeds the same script, before the UI script that uses it
+        let builder = html.find(arg_builder_js()).unwrap();
+        assert!(builder < html.find(r#"<script type="module">"#).unwrap());
+        assert!(arg_builder_js().contains("window.buildCliArgs = (config, formElement) =>"));
+        assert!(html.contains("return window.buildCliArgs(CONFIG, form);"));
+        assert!(html.contains("} = window.createArgBuilder(CONFIG, form);"));
+    }
+
+    #[test]
+    fn test_counter_levels() {
+        use clap::{Parser, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Verbosity
+            #[arg(short, action = clap::ArgAction::Count)]
+            verbose: u8,
+        }
+
+        let mut config = test_config(extract_field_descriptors_from_command(&TestArgs::command()));
+        assert!(generate_wasm_function_page(&config).contains(r#"<input type="number" id="verbose""#));
+
+        config.counter_levels.insert(
+            "verbose".to_string(),
+            ["Off", "Info", "Debug", "Trace"].map(String::from).to_vec(),
+        );
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"<input type="number" id="verbose""#));
+        assert!(html.contains(concat!(
+            r#"<div class="enum-buttons counter-levels" id="verbose" role="radiogroup">"#,
+            r#"<label class="enum-button"><input type="radio" name="verbose" value="0" checked><span>Off</span></label>"#,
+            r#"<label class="enum-button"><input type="radio" name="verbose" value="1"><span>Info</span></label>"#,
+        )));
+        assert!(html.contains(r#"value="3"><span>Trace</span></label></div>"#));
+        // Parsed command lines beyond the last level select it
+        assert!(html.contains("Math.min(count, element.options.length - 1)"));
+
+        // An empty list keeps the number input
+        config.counter_levels.insert("verbose".to_string(), Vec::new());
+        assert!(generate_wasm_function_page(&config).contains(r#"<input type="number" id="verbose""#));
+    }
+
+    #[test]
+    fn test_json_tree() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"<div class="json-tree-controls">"#));
+        assert!(html.contains(r#""json_tree":false"#));
+
+        config.json_tree = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<div class="json-tree-controls">"#,
+            r#"<button id="expandAllButton" type="button" data-i18n="expandAll">Expand all</button>"#,
+            r#"<button id="collapseAllButton" type="button" data-i18n="collapseAll">Collapse all</button></div>"#,
+        )));
+        assert!(html.contains(r#""json_tree":true"#));
+        // Non-JSON output falls back to the text rendering
+        assert!(html.contains("(CONFIG.json_tree && renderJsonTree(resultText)) || parseAnsiColors(resultText)"));
+
+        config.fields = vec![FieldDescriptor { name: "expandAllButton".to_string(), ..Default::default() }];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_hide_short_help_fields() {
+        use clap::{Parser, Subcommand, CommandFactory};
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Name
+            #[arg(long)]
+            name: Option<String>,
+
+            /// Tuning knob for experts
+            #[arg(long, hide_short_help = true)]
+            tuning: Option<u32>,
+
+            /// Still needed
+            #[arg(long, hide_short_help = true)]
+            token: String,
+
+            #[command(subcommand)]
+            command: Option<Commands>,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            Add {
+                /// Debug dump
+                #[arg(long, hide_short_help = true)]
+                dump: bool,
+            },
+        }
+
+        let cmd = TestArgs::command();
+        let mut config = WasmFunctionConfig {
+            subcommands: extract_subcommands_from_command(&cmd),
+            ..test_config(extract_field_descriptors_from_command(&cmd))
+        };
+        assert!(config.fields.iter().find(|f| f.name == "tuning").unwrap().hide_short_help);
+        assert!(!config.fields.iter().find(|f| f.name == "name").unwrap().hide_short_help);
+
+        // Shown like --help by default
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"id="tuning""#));
+        assert!(html.contains(r#"id="add-dump""#));
+
+        config.hide_short_help_fields = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"id="name""#));
+        assert!(!html.contains(r#"id="tuning""#));
+        assert!(!html.contains(r#"id="add-dump""#));
+        assert!(!config_to_json(&config).contains(r#""name":"tuning""#));
+        // Required without a default, so kept
+        assert!(html.contains(r#"id="token""#));
+    }
+
+    #[test]
+    fn test_pinned_fields() {
+        let mut config = test_config(vec![FieldDescriptor { name: "name".to_string(), ..Default::default() }]);
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"id="pinnedFields""#));
+        assert!(html.contains(r#""pinned_fields":false"#));
+
+        config.pinned_fields = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<form id="cliForm"><div class="form-section pinned-fields" id="pinnedFields" hidden>"#,
+            r#"<h2 data-i18n="pinnedFields">Pinned</h2></div><div class="form-section">"#,
+        )));
+        assert!(html.contains(r#""pinned_fields":true"#));
+
+        config.fields[0].name = "pinnedFields".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_usage() {
+        use clap::{Parser, Subcommand};
+
+        #[derive(Parser)]
+        #[command(name = "example")]
+        struct TestArgs {
+            /// Name
+            #[arg(long)]
+            name: String,
+
+            #[command(subcommand)]
+            command: Option<Commands>,
+        }
+
+        #[derive(Subcommand)]
+        enum Commands {
+            Add {
+                /// File to add
+                file: String,
+            },
+        }
+
+        let config = build_config_for_parser::<TestArgs>("example", "", "run_bind");
+        assert_eq!(config.usage.as_deref(), Some("example --name <NAME> [COMMAND]"));
+        assert_eq!(config.subcommands[0].usage.as_deref(), Some("example --name <NAME> add <FILE>"));
+
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(concat!(
+            r#"<div class="usage" id="usage"><span class="usage-label" data-i18n="usage">Usage</span>"#,
+            r#"<code class="usage-line">example --name &lt;NAME&gt; [COMMAND]</code>"#,
+            r#"<code class="usage-line" data-subcommand="add" hidden>example --name &lt;NAME&gt; add &lt;FILE&gt;</code></div>"#,
+        )));
+
+        // Hand-written configs have none
+        let config = WasmFunctionConfig { usage: None, ..config };
+        assert!(!generate_wasm_function_page(&config).contains(r#"<div class="usage""#));
+    }
+
+    #[test]
+    fn test_share_button() {
+        let mut config = test_config(vec![]);
+        let html = generate_wasm_function_page(&config);
+        assert!(!html.contains(r#"id="shareButton""#));
+        assert!(html.contains(r#""share_button":false"#));
+
+        config.share_button = true;
+        let html = generate_wasm_function_page(&config);
+        assert!(html.contains(r#"<button class="share-btn" id="shareButton" type="button" data-i18n="share">Share</button>"#));
+        assert!(html.contains(r#""share_button":true"#));
+    }
+
+    #[test]
+    fn test_external_scripts() {
+        let mut config = test_config(vec![]);
+        config.external_config = Some("./index.config.json".to_string());
+        config.external_scripts = Some(ExternalScripts {
+            i18n: "./i18n.js".to_string(),
+            arg_builder: "./arg-builder.js".to_string(),
+            page: "./index.ui.js".to_string(),
+        });
+        let html = generate_wasm_function_page(&config);
+
+        // Every script is loaded from its file, none has an inline body
+        let scripts: Vec<&str> = html.split("<script").skip(1).collect();
+        assert_eq!(scripts.len(), 3);
+        for script in &scripts {
+            let (tag, rest) = script.split_once('>').unwrap();
+            assert!(tag.contains(" src=\""), "script without src: {}", tag);
+            assert!(rest.starts_with("</script>"), "inline script body: {}", tag);
+        }
+        assert!(html.contains(r#"<script type="module" src="./index.ui.js"></script>"#));
+        assert!(page_script(&config).contains("const CONFIG_PATH = './index.config.json';"));
+
+        // The config stays inline unless it's external too
+        config.external_config = None;
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches("<script src=").count(), 2);
+        assert!(html.contains("window.CLI_CONFIG = "));
+    }
+
+    #[test]
+    fn test_reserved_ids_follow_rendered_elements() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(name = "test")]
+        struct TestArgs {
+            /// Print usage examples
+            #[arg(long)]
+            usage: bool,
+            /// Show the path to the command
+            #[arg(long)]
+            breadcrumb: bool,
+        }
+
+        let mut config = build_config_for_parser::<TestArgs>("test", "Test", "run_bind");
+        assert_eq!(config.validate(), Err(vec![ConfigError::ConflictingId { id: "usage".to_string() }]));
+
+        // Without the usage line, `--usage` gets the id to itself
+        config.usage = None;
+        assert_eq!(config.validate(), Ok(()));
+        let html = generate_wasm_function_page(&config);
+        assert_eq!(html.matches(r#" id="usage""#).count(), 1);
+        assert!(html.contains(r#"<input type="checkbox" id="usage" name="usage""#));
+
+        // A feature's ids are only taken while it's enabled
+        config.breadcrumb = true;
+        assert_eq!(config.validate(), Err(vec![ConfigError::ConflictingId { id: "breadcrumb".to_string() }]));
+    }
+}
@@ -0,0 +1,15 @@
+// ============================================================================
+// Web Component Wrapper
+// ============================================================================
+// Attaches the template to a shadow root so the host page's styles and the
+// UI's styles don't affect each other
+customElements.define('clap-web-ui', class extends HTMLElement {
+    connectedCallback() {
+        if (this.shadowRoot) return;
+        const template = document.getElementById('clap-web-ui-template');
+        this.attachShadow({ mode: 'open' }).appendChild(template.content.cloneNode(true));
+    }
+});
+
+// The UI scripts look up their elements here instead of in the document
+window.CLI_ROOT = document.querySelector('clap-web-ui').shadowRoot;
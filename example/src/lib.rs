@@ -18,6 +18,7 @@ pub fn init_panic_hook() {
 #[command(version = "1.0")]
 #[command(about = "Example CLI with various Clap features",
     long_about = "This is an example to show the features of the web UI generator for Rust cli tool built with Clap")]
+#[command(after_help = "Examples:\n  $ example -s hello -b -cc\n  $ example --enum-field option-c -v one -v two sub1 \"some text\"")]
 pub struct Opt {
     /// Optional string field
     #[arg(short = 's', long)]
@@ -117,8 +118,11 @@ fn inner_print() {
     wprintln!("This is from internal");
 }
 
+/// The bound function's parameter may also be a type alias
+pub type Cli = Opt;
+
 #[web_ui_bind]
-pub fn process(opt: &Opt) {
+pub fn process(opt: &Cli) {
     inner_print();
     wprintln!("Processing with options:");
     wprintln!("  string_field: {:?}", opt.string_field);
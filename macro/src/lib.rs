@@ -74,10 +74,30 @@ pub fn wprintln(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Binds a function taking `&T` (where `T: clap::Parser`) to WASM and generates
+/// `generate_{fn}_ui` for building its web UI (plus `generate_{fn}_config` for
+/// its configuration, to adjust before rendering, and `generate_{fn}_schema`
+/// for a JSON Schema of its arguments)
+///
+/// `T` may be any path that resolves to a concrete type, including a type alias
+/// (`type Cli = Opt;`) or a concrete instantiation of a generic type
+/// (`type Cli = Opt<Json>;`). Generic functions and `&impl Parser` parameters
+/// aren't supported, since the WASM binding needs one concrete type to parse into.
 #[proc_macro_attribute]
 pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
 
+    // The binding parses into a single concrete type, so there's nothing to
+    // instantiate generics with
+    if !input_fn.sig.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input_fn.sig.generics,
+            "#[web_ui_bind] functions can't be generic; take a concrete type (a type alias such as `type Cli = Opt<Json>;` works)",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     let fn_name = &input_fn.sig.ident;
     let fn_vis = &input_fn.vis;
     let fn_block = &input_fn.block;
@@ -100,6 +120,15 @@ pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
             panic!("Parameter must be a reference");
         };
 
+        if let syn::Type::ImplTrait(_) = &**inner_type {
+            return syn::Error::new_spanned(
+                inner_type,
+                "#[web_ui_bind] needs a concrete parameter type, not `impl Trait`",
+            )
+            .to_compile_error()
+            .into();
+        }
+
         (param_name, inner_type)
     } else {
         panic!("Function must have typed parameters");
@@ -107,6 +136,8 @@ pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let bind_fn_name = syn::Ident::new(&format!("{}_bind", fn_name), fn_name.span());
     let ui_gen_fn_name = syn::Ident::new(&format!("generate_{}_ui", fn_name), fn_name.span());
+    let config_gen_fn_name = syn::Ident::new(&format!("generate_{}_config", fn_name), fn_name.span());
+    let schema_gen_fn_name = syn::Ident::new(&format!("generate_{}_schema", fn_name), fn_name.span());
 
     // Use a fixed module name since we want one println! override for the whole module
     let capture_mod_name = syn::Ident::new("__web_ui_capture", fn_name.span());
@@ -133,45 +164,92 @@ pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
-    let expanded = quote! {
-        // Generate the capture infrastructure
-        #[cfg(target_arch = "wasm32")]
-        #[allow(dead_code)]
-        mod __web_ui_capture {
-            use std::cell::RefCell;
-            use std::fmt::Write;
+    // Output buffer: per thread by default, or shared by all threads (so output
+    // from spawned threads is captured too) with the `threaded-capture` feature
+    let capture_mod = if cfg!(feature = "threaded-capture") {
+        quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[allow(dead_code)]
+            mod __web_ui_capture {
+                use std::fmt::Write;
+                use std::sync::{Mutex, MutexGuard};
 
-            thread_local! {
-                pub static BUFFER: RefCell<String> = RefCell::new(String::new());
-            }
+                pub static BUFFER: Mutex<String> = Mutex::new(String::new());
 
-            pub fn capture<F: FnOnce()>(f: F) -> String {
-                BUFFER.with(|buf| buf.borrow_mut().clear());
-                f();
-                BUFFER.with(|buf| buf.borrow().clone())
-            }
+                // A panicking writer doesn't make the captured text unusable
+                fn buffer() -> MutexGuard<'static, String> {
+                    BUFFER.lock().unwrap_or_else(|e| e.into_inner())
+                }
 
-            pub fn capture_result<F, E>(f: F) -> Result<String, E>
-            where
-                F: FnOnce() -> Result<(), E>,
-            {
-                BUFFER.with(|buf| buf.borrow_mut().clear());
-                f()?;
-                Ok(BUFFER.with(|buf| buf.borrow().clone()))
-            }
+                pub fn capture<F: FnOnce()>(f: F) -> String {
+                    buffer().clear();
+                    f();
+                    buffer().clone()
+                }
+
+                pub fn capture_result<F, E>(f: F) -> Result<String, E>
+                where
+                    F: FnOnce() -> Result<(), E>,
+                {
+                    buffer().clear();
+                    f()?;
+                    Ok(buffer().clone())
+                }
 
-            pub fn write_fmt(args: std::fmt::Arguments) {
-                BUFFER.with(|buf| {
-                    let _ = writeln!(buf.borrow_mut(), "{}", args);
-                });
+                pub fn write_fmt(args: std::fmt::Arguments) {
+                    let _ = writeln!(buffer(), "{}", args);
+                }
+
+                pub fn write_fmt_no_newline(args: std::fmt::Arguments) {
+                    let _ = write!(buffer(), "{}", args);
+                }
             }
+        }
+    } else {
+        quote! {
+            #[cfg(target_arch = "wasm32")]
+            #[allow(dead_code)]
+            mod __web_ui_capture {
+                use std::cell::RefCell;
+                use std::fmt::Write;
 
-            pub fn write_fmt_no_newline(args: std::fmt::Arguments) {
-                BUFFER.with(|buf| {
-                    let _ = write!(buf.borrow_mut(), "{}", args);
-                });
+                thread_local! {
+                    pub static BUFFER: RefCell<String> = RefCell::new(String::new());
+                }
+
+                pub fn capture<F: FnOnce()>(f: F) -> String {
+                    BUFFER.with(|buf| buf.borrow_mut().clear());
+                    f();
+                    BUFFER.with(|buf| buf.borrow().clone())
+                }
+
+                pub fn capture_result<F, E>(f: F) -> Result<String, E>
+                where
+                    F: FnOnce() -> Result<(), E>,
+                {
+                    BUFFER.with(|buf| buf.borrow_mut().clear());
+                    f()?;
+                    Ok(BUFFER.with(|buf| buf.borrow().clone()))
+                }
+
+                pub fn write_fmt(args: std::fmt::Arguments) {
+                    BUFFER.with(|buf| {
+                        let _ = writeln!(buf.borrow_mut(), "{}", args);
+                    });
+                }
+
+                pub fn write_fmt_no_newline(args: std::fmt::Arguments) {
+                    BUFFER.with(|buf| {
+                        let _ = write!(buf.borrow_mut(), "{}", args);
+                    });
+                }
             }
         }
+    };
+
+    let expanded = quote! {
+        // Generate the capture infrastructure
+        #capture_mod
 
         // Original function (unchanged)
         #(#fn_attrs)*
@@ -183,8 +261,10 @@ pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
         pub fn #bind_fn_name(
             args: Vec<String>
         ) -> Result<String, wasm_bindgen::prelude::JsValue> {
-            // Prepend program name (required by clap)
-            let mut cli_args = vec!["program".to_string()];
+            // Prepend the program name (required by clap); use the command's
+            // bin_name or name so usage and error messages match the real binary
+            let program = clap_web_code_gen::program_name(&<#param_type as clap::CommandFactory>::command());
+            let mut cli_args = vec![program];
             cli_args.extend(args);
 
             let #param_name = <#param_type as clap::Parser>::try_parse_from(&cli_args)
@@ -203,7 +283,8 @@ pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
         ///
         /// This function is automatically generated by the `#[web_ui_bind]` macro.
         /// It creates a complete HTML page that allows users to interact with the
-        /// WASM-bound function through a web interface.
+        /// WASM-bound function through a web interface, with the command's usage
+        /// line shown above the form.
         ///
         /// # Arguments
         ///
@@ -212,12 +293,40 @@ pub fn web_ui_bind(_attr: TokenStream, item: TokenStream) -> TokenStream {
         ///
         /// # Returns
         ///
-        /// A String containing the complete HTML page
-        pub fn #ui_gen_fn_name(package_name: &str, page_title: &str) -> String {
-            clap_web_code_gen::generate_ui_for_parser_with_function::<#param_type>(
+        /// A String containing the complete HTML page, or the problems
+        /// `WasmFunctionConfig::validate` reports, e.g. an argument whose id
+        /// collides with one of the page's own elements
+        pub fn #ui_gen_fn_name(
+            package_name: &str,
+            page_title: &str
+        ) -> Result<String, Vec<clap_web_code_gen::ConfigError>> {
+            let config = clap_web_code_gen::build_config_for_parser::<#param_type>(
                 package_name,
                 page_title,
                 #bind_fn_name_str
+            );
+            config.validate()?;
+            Ok(clap_web_code_gen::generate_wasm_function_page(&config))
+        }
+
+        /// Builds the web UI configuration for this function without rendering it
+        ///
+        /// Adjust the returned config and pass it to
+        /// `clap_web_code_gen::generate_wasm_function_page`.
+        pub fn #config_gen_fn_name(package_name: &str, page_title: &str) -> clap_web_code_gen::WasmFunctionConfig {
+            clap_web_code_gen::build_config_for_parser::<#param_type>(
+                package_name,
+                page_title,
+                #bind_fn_name_str
+            )
+        }
+
+        /// Generates a JSON Schema (draft 2020-12) of this function's arguments,
+        /// pretty-printed
+        pub fn #schema_gen_fn_name() -> String {
+            format!(
+                "{:#}",
+                clap_web_code_gen::export_json_schema(&<#param_type as clap::CommandFactory>::command())
             )
         }
     };